@@ -54,13 +54,16 @@ pub mod coretime_market {
 	};
 	use openbrush::{contracts::traits::psp34::Id, storage::Mapping, traits::Storage};
 	use primitives::{
-
+		calendar::block_to_timeslice,
 		coretime::{RawRegionId, Region, Timeslice, CORE_MASK_BIT_LEN},
 
 		ensure, Version,
 	};
-	use sp_arithmetic::{traits::SaturatedConversion, FixedPointNumber, FixedU128};
-	use xc_regions::{traits::RegionMetadataRef, PSP34Ref};
+	use sp_arithmetic::{FixedPointNumber, FixedU128};
+	use xc_regions::{
+		traits::{RegionMetadataRef, RegionStateRef},
+		PSP34Ref,
+	};
 
 	#[ink(storage)]
 	#[derive(Storage)]
@@ -211,6 +214,19 @@ pub mod coretime_market {
 					.map_err(MarketError::XcRegionsMetadataError)?;
 
 
+			// The metadata of an optimistically initialized region can't be trusted until it gets
+			// finalized.
+			ensure!(
+				!RegionStateRef::is_pending(&self.config.xc_regions_contract, id.clone()),
+				MarketError::RegionPending
+			);
+
+			// Finally assigned regions no longer exist on the Coretime chain.
+			ensure!(
+				!RegionStateRef::is_finally_assigned(&self.config.xc_regions_contract, id.clone()),
+				MarketError::RegionAssigned
+			);
+
 			let current_timeslice = self.current_timeslice();
 
 			// It doesn't make sense to list a region that expired.
@@ -454,7 +470,7 @@ pub mod coretime_market {
 			let latest_rc_block =
 				self.env().extension().relay_chain_block_number().unwrap_or_default();
 
-			(latest_rc_block / self.config.timeslice_period).saturated_into()
+			block_to_timeslice(latest_rc_block, self.config.timeslice_period)
 
 		}
 
@@ -462,7 +478,7 @@ pub mod coretime_market {
 		pub(crate) fn current_timeslice(&self) -> Timeslice {
 			let latest_block = self.env().block_number();
 
-			(latest_block / self.config.timeslice_period).saturated_into()
+			block_to_timeslice(latest_block, self.config.timeslice_period)
 
 		}
 
@@ -480,17 +496,18 @@ pub mod coretime_market {
 		use environment::ExtendedEnvironment;
 		use ink_e2e::MessageBuilder;
 
-		use primitives::coretime::TIMESLICE_PERIOD;
+		use primitives::calendar::TIMESLICE_PERIOD;
 
 		use xc_regions::xc_regions::XcRegionsRef;
 
 		type E2EResult<T> = Result<T, Box<dyn std::error::Error>>;
 
 		const REQUIRED_DEPOSIT: Balance = 1_000;
+		const REGIONS_COLLECTION_ID: u32 = 42;
 
 		#[ink_e2e::test(environment = ExtendedEnvironment)]
 		async fn constructor_works(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
-			let constructor = XcRegionsRef::new();
+			let constructor = XcRegionsRef::new(REGIONS_COLLECTION_ID, Default::default());
 			let xc_regions_acc_id = client
 				.instantiate("xc-regions", &ink_e2e::alice(), constructor, 0, None)
 				.await
@@ -4,7 +4,10 @@ use ink::env::{
 	DefaultEnvironment,
 };
 use openbrush::traits::BlockNumber;
-use primitives::coretime::{CoreMask, Region, Timeslice, TIMESLICE_PERIOD};
+use primitives::{
+	calendar::{timeslice_to_block, TIMESLICE_PERIOD},
+	coretime::{CoreMask, Region, Timeslice},
+};
 
 #[ink::test]
 fn calculate_region_price_works() {
@@ -85,7 +88,7 @@ fn advance_block() {
 }
 
 fn timeslice_to_block_number(timeslice: Timeslice) -> BlockNumber {
-	timeslice * TIMESLICE_PERIOD
+	timeslice_to_block(timeslice, TIMESLICE_PERIOD)
 }
 
 fn get_default_accounts() -> DefaultAccounts<DefaultEnvironment> {
@@ -60,6 +60,10 @@ pub enum MarketError {
 	XcRegionsPsp34Error(PSP34Error),
 	/// An error occured when calling the xc-regions contract through the metadata interface.
 	XcRegionsMetadataError(XcRegionsError),
+	/// The metadata of the specified region can still be challenged.
+	RegionPending,
+	/// The region is finally assigned to a task, so it can't change hands.
+	RegionAssigned,
 }
 
 impl core::fmt::Display for MarketError {
@@ -77,20 +81,26 @@ impl core::fmt::Display for MarketError {
 			MarketError::NotAllowed => write!(f, "NotAllowed"),
 			MarketError::XcRegionsPsp34Error(e) => write!(f, "{:?}", e),
 			MarketError::XcRegionsMetadataError(e) => write!(f, "{}", e),
+			MarketError::RegionPending => write!(f, "RegionPending"),
+			MarketError::RegionAssigned => write!(f, "RegionAssigned"),
 		}
 	}
 }
 
 #[derive(scale::Decode, scale::Encode, Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Listing {
 	/// The `AccountId` selling the specific region.
+	#[cfg_attr(feature = "serde", serde(with = "primitives::serde_utils::account_id"))]
 	pub seller: AccountId,
 	/// The price per a single timeslice.
+	#[cfg_attr(feature = "serde", serde(with = "primitives::serde_utils::u128_string"))]
 	pub timeslice_price: Balance,
 	/// The `AccountId` receiving the payment from the sale.
 	///
 	/// If not set specified otherwise this should be the `seller` account.
+	#[cfg_attr(feature = "serde", serde(with = "primitives::serde_utils::account_id"))]
 	pub sale_recepient: AccountId,
 	/// The metadata version of the region listed on sale. Used to prevent front running attacks.
 	pub metadata_version: Version,
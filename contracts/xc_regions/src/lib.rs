@@ -16,6 +16,7 @@
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
 #![feature(min_specialization)]
 
+pub mod render;
 pub mod traits;
 pub mod types;
 
@@ -24,32 +25,59 @@ mod tests;
 
 pub use crate::xc_regions::PSP34Ref;
 
-// NOTE: This should be the collection ID of the underlying region collection.
-pub const REGIONS_COLLECTION_ID: u32 = 42;
-
-#[openbrush::implementation(PSP34, PSP34Enumerable)]
+#[openbrush::implementation(PSP34, PSP34Enumerable, PSP34Metadata, Ownable)]
 #[openbrush::contract(env = environment::ExtendedEnvironment)]
 pub mod xc_regions {
 	use crate::{
-		traits::{regionmetadata_external, RegionMetadata},
-		types::{VersionedRegion, XcRegionsError},
-		REGIONS_COLLECTION_ID,
+		render,
+		traits::{regionmetadata_external, regionstate_external, RegionMetadata, RegionState},
+		types::{
+			Assignment, Attestation, AttestationNonce, Backend, ChallengeEvidence, FeeAsset,
+			MetadataHistoryPage, MetadataRecord, OptimisticConfig, PendingRegion, PooledRegion,
+			ReapConfig, RegionFilter, RegionsPage, RelayerKey, TeleportConfig, TransactConfig,
+			VersionedRegion, XcRegionsError, COLLECTION_NAME, COLLECTION_SYMBOL, MAX_BATCH_SIZE,
+			MAX_PAGE_SIZE, MAX_STATE_ROOT_AGE,
+		},
 	};
+	#[cfg(not(test))]
+	use block_number_extension::BlockNumberProviderExtension;
 	use ink::{
 		codegen::{EmitEvent, Env},
-		storage::Mapping,
+		prelude::vec::Vec,
+		storage::{Lazy, Mapping},
+	};
+	use nfts_extension::NftsExtension;
+	use openbrush::{
+		contracts::psp34::PSP34Error,
+		modifiers,
+		traits::{Storage, String},
 	};
-	use openbrush::traits::Storage;
 	use primitives::{
-		coretime::{RawRegionId, Region, RegionId},
+		assets::AssetsCall,
+		broker::BrokerCall,
+		calendar::{block_to_timeslice, TIMESLICE_PERIOD},
+		coretime::{
+			CoreIndex, CoreMask, Finality, ParaId, RawRegionId, Region, RegionId, Timeslice,
+		},
 		ensure,
-		uniques::{ItemDetails, UniquesCall},
+		nfts::NftsCall,
+		pallet_xcm::{
+			reserve_transfer_region, reserve_withdraw_region, reserve_withdraw_region_to,
+			sibling_account, transact_on_coretime, Weight, XcmCall, XcmCallIndices,
+		},
+		proof::read_region_record,
+		uniques::{CollectionId, ItemDetails, UniquesCall},
 		RuntimeCall, Version,
 	};
+	#[cfg(not(test))]
+	use signature_extension::SignatureVerifierExtension;
+	#[cfg(not(test))]
+	use state_root_extension::StateRootProviderExtension;
 	use uniques_extension::UniquesExtension;
-
-	#[cfg(test)]
-	use primitives::uniques::CollectionId;
+	use xcm::{
+		v3::{Junction::Parachain, Junctions::X1, MultiLocation, Xcm},
+		VersionedMultiLocation, VersionedXcm,
+	};
 
 	#[openbrush::wrapper]
 	pub type PSP34Ref = dyn PSP34 + PSP34Enumerable;
@@ -61,12 +89,82 @@ pub mod xc_regions {
 		psp34: psp34::Data,
 		#[storage_field]
 		enumerable: enumerable::Data,
+		#[storage_field]
+		metadata: metadata::Data,
+		#[storage_field]
+		ownable: ownable::Data,
+		/// The collection id of the underlying regions. Set on contract initialization. Can't be
+		/// changed afterwards.
+		pub regions_collection_id: CollectionId,
+		/// The pallet holding the underlying regions, along with the indices used for encoding the
+		/// calls dispatched to it through `call_runtime`. Set on contract initialization. Can't
+		/// be changed afterwards.
+		pub backend: Backend,
 		/// A mapping that links RawRegionId to its corresponding region metadata.
 		pub regions: Mapping<RawRegionId, Region>,
 		/// A mapping that keeps track of the metadata version for each region.
 		///
 		/// This version gets incremented for a region each time it gets re-initialized.
 		pub metadata_versions: Mapping<RawRegionId, Version>,
+		/// An append-only log of all the metadata ever stored for each region, indexed by the
+		/// position of the entry in the log of the region.
+		///
+		/// Unlike `regions` and `metadata_versions`, this is kept when a region gets removed.
+		pub metadata_history: Mapping<(RawRegionId, u32), MetadataRecord>,
+		/// The number of entries in the metadata history of each region.
+		pub metadata_history_len: Mapping<RawRegionId, u32>,
+		/// The configuration of the optimistic initialization. If not set, regions can't be
+		/// initialized optimistically. Can be changed by the owner of the contract.
+		pub optimistic_config: Option<OptimisticConfig>,
+		/// A mapping of the optimistically initialized regions whose metadata can still be
+		/// challenged.
+		pub pending_regions: Mapping<RawRegionId, PendingRegion>,
+		/// The keys of the relayers whose attestations are accepted by `init_with_attestations`.
+		/// Can be changed by the owner of the contract.
+		pub relayers: Lazy<Vec<RelayerKey>>,
+		/// The number of distinct relayer attestations required for initializing a region. Zero
+		/// disables the initialization through attestations. Can be changed by the owner of the
+		/// contract.
+		pub attestation_threshold: u32,
+		/// A mapping that keeps track of the nonce the next attestations of each region must
+		/// include.
+		pub attestation_nonces: Mapping<RawRegionId, AttestationNonce>,
+		/// A mapping from the wrapped regions derived within the contract, e.g. through
+		/// partitioning, to the metadata of the underlying region they were derived from.
+		///
+		/// The underlying region stays in the contract until all the regions derived from it are
+		/// merged back, or until all of them expired and got reaped.
+		pub underlying_regions: Mapping<RawRegionId, Region>,
+		/// The number of wrapped regions derived from each underlying region which still exist,
+		/// keyed by the identifier of the underlying region.
+		pub derived_pieces: Mapping<RawRegionId, u32>,
+		/// The configuration for sending regions back to the Coretime chain. If not set, regions
+		/// can't be sent back through the contract. Can be changed by the owner of the contract.
+		pub teleport_config: Option<TeleportConfig>,
+		/// The configuration for dispatching calls on the Coretime chain. If not set, regions
+		/// can't be assigned through the contract. Can be changed by the owner of the contract.
+		pub transact_config: Option<TransactConfig>,
+		/// A mapping of the wrapped regions assigned to a task through the contract.
+		pub assignments: Mapping<RawRegionId, Assignment>,
+		/// The wrapped regions whose underlying region got moved to the account of the contract
+		/// on the Coretime chain for dispatching calls on it. These can't be removed until their
+		/// underlying region is sent back to the contract through `retrieve_from_coretime`.
+		pub regions_on_coretime: Mapping<RawRegionId, ()>,
+		/// A mapping of the wrapped regions contributed to the instantaneous coretime pool
+		/// through the contract, whose revenue hasn't been fully claimed yet.
+		pub pooled_regions: Mapping<RawRegionId, PooledRegion>,
+		/// The configuration for reaping expired regions. If not set, expired regions can't be
+		/// reaped. Can be changed by the owner of the contract.
+		pub reap_config: Option<ReapConfig>,
+		/// The funds out of which the rewards for reaping expired regions are paid.
+		pub reap_reward_pool: Balance,
+		/// The number of relay chain blocks in a timeslice, used for determining the current
+		/// timeslice. Can be changed by the owner of the contract.
+		pub timeslice_period: BlockNumber,
+		/// The number of Coretime chain blocks by which the state root a storage proof is verified
+		/// against may lag behind the latest known state root. Can be changed by the owner of the
+		/// contract.
+		pub max_state_root_age: BlockNumber,
 		// Mock chain extension state only used for integration testing.
 		#[cfg(test)]
 		pub items: Mapping<
@@ -79,6 +177,27 @@ pub mod xc_regions {
 			AccountId,
 			Vec<(primitives::uniques::CollectionId, primitives::coretime::RawRegionId)>,
 		>,
+		// Mock chain extension state only used for integration testing.
+		#[cfg(test)]
+		pub frozen_collections: Mapping<CollectionId, ()>,
+		// Mock chain extension state only used for integration testing.
+		#[cfg(test)]
+		pub state_roots: Mapping<BlockNumber, Hash>,
+		// Mock chain extension state only used for integration testing.
+		#[cfg(test)]
+		pub latest_coretime_block: BlockNumber,
+		// Mock chain extension state only used for integration testing.
+		#[cfg(test)]
+		pub executed_xcms: Mapping<RawRegionId, Vec<u8>>,
+		// Mock chain extension state only used for integration testing.
+		#[cfg(test)]
+		pub sent_xcms: Lazy<Vec<Vec<u8>>>,
+		// Mock chain extension state only used for integration testing.
+		#[cfg(test)]
+		pub fee_allowances: Mapping<AccountId, Balance>,
+		// Mock chain extension state only used for integration testing.
+		#[cfg(test)]
+		pub collected_fees: Balance,
 	}
 
 	#[ink(event)]
@@ -100,9 +219,232 @@ pub mod xc_regions {
 		pub(crate) region_id: RawRegionId,
 	}
 
+	#[ink(event)]
+	pub struct RegionAttested {
+		/// The identifier of the region that got initialized through relayer attestations.
+		#[ink(topic)]
+		pub(crate) region_id: RawRegionId,
+		/// The keys of the relayers which vouched for the metadata.
+		pub(crate) attestors: Vec<RelayerKey>,
+		/// The nonce included in the attestations.
+		pub(crate) nonce: AttestationNonce,
+	}
+
+	#[ink(event)]
+	pub struct RegionPartitioned {
+		/// The identifier of the region that got partitioned.
+		#[ink(topic)]
+		pub(crate) region_id: RawRegionId,
+		/// The timeslice at which the region got partitioned.
+		pub(crate) pivot: Timeslice,
+		/// The identifiers of the two resulting regions, along with their metadata versions.
+		pub(crate) new_regions: ((RawRegionId, Version), (RawRegionId, Version)),
+	}
+
+	#[ink(event)]
+	pub struct RegionInterlaced {
+		/// The identifier of the region that got interlaced.
+		#[ink(topic)]
+		pub(crate) region_id: RawRegionId,
+		/// The mask of the first resulting region.
+		pub(crate) pivot_mask: CoreMask,
+		/// The identifiers of the two resulting regions, along with their metadata versions.
+		pub(crate) new_regions: ((RawRegionId, Version), (RawRegionId, Version)),
+	}
+
+	#[ink(event)]
+	pub struct RegionsMerged {
+		/// The identifiers of the regions that got merged.
+		pub(crate) region_ids: (RawRegionId, RawRegionId),
+		/// The identifier of the resulting region.
+		#[ink(topic)]
+		pub(crate) new_region_id: RawRegionId,
+		/// The metadata version of the resulting region.
+		pub(crate) version: Version,
+	}
+
+	#[ink(event)]
+	pub struct RegionTeleported {
+		/// The identifier of the region that got sent back to the Coretime chain.
+		#[ink(topic)]
+		pub(crate) region_id: RawRegionId,
+		/// The account to which the region got sent.
+		pub(crate) owner: AccountId,
+		/// The location of the Coretime chain to which the region got sent.
+		pub(crate) dest: VersionedMultiLocation,
+	}
+
+	#[ink(event)]
+	pub struct AssignmentRequested {
+		/// The identifier of the region whose assignment got requested.
+		#[ink(topic)]
+		pub(crate) region_id: RawRegionId,
+		/// The parachain to which the region is to be assigned.
+		pub(crate) task: ParaId,
+		/// Whether the assignment can still be changed.
+		pub(crate) finality: Finality,
+	}
+
+	#[ink(event)]
+	pub struct RegionAssigned {
+		/// The identifier of the region that got assigned.
+		#[ink(topic)]
+		pub(crate) region_id: RawRegionId,
+		/// The parachain to which the region got assigned.
+		pub(crate) task: ParaId,
+		/// Whether the assignment can still be changed.
+		pub(crate) finality: Finality,
+	}
+
+	#[ink(event)]
+	pub struct RetrievalRequested {
+		/// The identifier of the region whose underlying region is to be sent back from the
+		/// Coretime chain.
+		#[ink(topic)]
+		pub(crate) region_id: RawRegionId,
+	}
+
+	#[ink(event)]
+	pub struct RegionPooled {
+		/// The identifier of the region that got contributed to the pool.
+		#[ink(topic)]
+		pub(crate) region_id: RawRegionId,
+		/// The account on the Coretime chain to which the revenue is paid.
+		pub(crate) payee: AccountId,
+	}
+
+	#[ink(event)]
+	pub struct RevenueClaimRequested {
+		/// The identifier of the pooled region whose revenue is to be claimed.
+		#[ink(topic)]
+		pub(crate) region_id: RawRegionId,
+		/// The timeslices whose revenue is to be claimed, as a `[from, to)` range.
+		pub(crate) timeslices: (Timeslice, Timeslice),
+	}
+
+	#[ink(event)]
+	pub struct RevenueClaimed {
+		/// The identifier of the pooled region whose revenue got claimed.
+		#[ink(topic)]
+		pub(crate) region_id: RawRegionId,
+		/// The timeslices whose revenue got claimed, as a `[from, to)` range.
+		pub(crate) timeslices: (Timeslice, Timeslice),
+	}
+
+	#[ink(event)]
+	pub struct RegionReaped {
+		/// The identifier of the expired region that got reaped.
+		#[ink(topic)]
+		pub(crate) region_id: RawRegionId,
+		/// The account that reaped the region.
+		pub(crate) reaper: AccountId,
+	}
+
+	#[ink(event)]
+	pub struct RegionChallenged {
+		/// The identifier of the region whose metadata got successfully challenged.
+		#[ink(topic)]
+		pub(crate) region_id: RawRegionId,
+		/// The account which challenged the metadata and received the bond.
+		pub(crate) challenger: AccountId,
+		/// The corrected metadata.
+		pub(crate) metadata: Region,
+		/// The version of the corrected metadata.
+		pub(crate) version: Version,
+	}
+
+	#[ink(event)]
+	pub struct RegionFinalized {
+		/// The identifier of the region whose metadata became final.
+		#[ink(topic)]
+		pub(crate) region_id: RawRegionId,
+	}
+
+	#[ink(event)]
+	pub struct RegionTransferred {
+		/// The identifier of the region that got transferred.
+		pub(crate) region_id: RawRegionId,
+		/// The previous owner of the region, or `None` if the region got minted.
+		#[ink(topic)]
+		pub(crate) from: Option<AccountId>,
+		/// The new owner of the region, or `None` if the region got burned.
+		#[ink(topic)]
+		pub(crate) to: Option<AccountId>,
+		/// The core to which the region belongs.
+		#[ink(topic)]
+		pub(crate) core: CoreIndex,
+		/// The metadata of the region along with its version.
+		pub(crate) metadata: VersionedRegion,
+	}
+
 	#[overrider(PSP34)]
 	fn collection_id(&self) -> Id {
-		Id::U32(REGIONS_COLLECTION_ID)
+		Id::U32(self.regions_collection_id)
+	}
+
+	#[overrider(PSP34Metadata)]
+	fn get_attribute(&self, id: Id, key: String) -> Option<String> {
+		// The attributes of wrapped regions are computed from their metadata, so they always
+		// reflect its latest version.
+		if let Id::U128(_) = id {
+			return RegionMetadata::get_metadata(self, id).ok()?.attribute(&key)
+		}
+
+		metadata::PSP34MetadataImpl::get_attribute(self, id, key)
+	}
+
+	#[overrider(psp34::Internal)]
+	fn _before_token_transfer(
+		&mut self,
+		from: Option<&AccountId>,
+		to: Option<&AccountId>,
+		id: &Id,
+	) -> Result<(), PSP34Error> {
+		// Finally assigned and pooled regions no longer exist on the Coretime chain, so they
+		// can't change hands. Pending regions can't change hands either, since the bond of their
+		// initializer vouches for their metadata. Minting and burning is still allowed.
+		if let (Some(_), Some(_), Id::U128(region_id)) = (from, to, id) {
+			ensure!(
+				!self.pending_regions.contains(region_id),
+				PSP34Error::Custom(String::from("RegionPending"))
+			);
+			let finality = self.assignments.get(region_id).map(|a| a.finality);
+			ensure!(
+				finality != Some(Finality::Final),
+				PSP34Error::Custom(String::from("RegionAssigned"))
+			);
+			ensure!(
+				!self.pooled_regions.contains(region_id),
+				PSP34Error::Custom(String::from("RegionPooled"))
+			);
+		}
+
+		Ok(())
+	}
+
+	#[overrider(psp34::Internal)]
+	fn _after_token_transfer(
+		&mut self,
+		from: Option<&AccountId>,
+		to: Option<&AccountId>,
+		id: &Id,
+	) -> Result<(), PSP34Error> {
+		// Minting and burning is reported as well, so that the ownership of the regions can be
+		// tracked from this event alone. The metadata is stored before minting and removed after
+		// burning, so it is available in both cases.
+		if let Id::U128(region_id) = id {
+			if let Ok(metadata) = RegionMetadata::get_metadata(self, id.clone()) {
+				self.env().emit_event(RegionTransferred {
+					region_id: *region_id,
+					from: from.copied(),
+					to: to.copied(),
+					core: metadata.region.core,
+					metadata,
+				});
+			}
+		}
+
+		Ok(())
 	}
 
 	impl RegionMetadata for XcRegions {
@@ -135,46 +477,10 @@ pub mod xc_regions {
 			let caller = self.env().caller();
 
 			let Id::U128(raw_region_id) = id else { return Err(XcRegionsError::InvalidRegionId) };
+			self._ensure_can_initialize(caller, raw_region_id)?;
+			self._ensure_matching_metadata(raw_region_id, &region)?;
 
-			ensure!(
-				Some(caller) == self._uniques_owner(raw_region_id),
-				XcRegionsError::CannotInitialize
-			);
-
-			// Cannot initialize a region that already has metadata stored.
-			ensure!(self.regions.get(raw_region_id).is_none(), XcRegionsError::CannotInitialize);
-
-			// Do a sanity check to ensure that the provided region metadata matches with the
-			// metadata extracted from the region id.
-			let region_id = RegionId::from(raw_region_id);
-			ensure!(region_id.begin == region.begin, XcRegionsError::InvalidMetadata);
-			ensure!(region_id.core == region.core, XcRegionsError::InvalidMetadata);
-			ensure!(region_id.mask == region.mask, XcRegionsError::InvalidMetadata);
-
-			// After passing all checks we will transfer the region to the contract and mint a
-			// wrapped xcRegion token.
-			let contract = self.env().account_id();
-			self._transfer(raw_region_id, contract)?;
-
-			let new_version = if let Some(version) = self.metadata_versions.get(raw_region_id) {
-				version.saturating_add(1)
-			} else {
-				Default::default()
-			};
-
-			self.metadata_versions.insert(raw_region_id, &new_version);
-			self.regions.insert(raw_region_id, &region);
-
-			psp34::InternalImpl::_mint_to(self, caller, Id::U128(raw_region_id))
-				.map_err(XcRegionsError::Psp34)?;
-
-			self.env().emit_event(RegionInitialized {
-				region_id: raw_region_id,
-				metadata: region,
-				version: new_version,
-			});
-
-			Ok(())
+			self._init(caller, raw_region_id, region)
 		}
 
 		/// A function to retrieve all metadata associated with a specific region.
@@ -218,13 +524,7 @@ pub mod xc_regions {
 		fn remove(&mut self, id: Id) -> Result<(), XcRegionsError> {
 			let Id::U128(region_id) = id else { return Err(XcRegionsError::InvalidRegionId) };
 
-			let owner =
-				psp34::PSP34Impl::owner_of(self, id.clone()).ok_or(XcRegionsError::CannotRemove)?;
-
-			ensure!(owner == self.env().caller(), XcRegionsError::CannotRemove);
-			self.regions.remove(region_id);
-
-			psp34::InternalImpl::_burn_from(self, owner, id).map_err(XcRegionsError::Psp34)?;
+			let owner = self._remove(region_id)?;
 			self._transfer(region_id, owner)?;
 
 			self.env().emit_event(RegionRemoved { region_id });
@@ -232,82 +532,2135 @@ pub mod xc_regions {
 		}
 	}
 
-	impl XcRegions {
-		#[ink(constructor)]
-		pub fn new() -> Self {
-			Default::default()
+	impl RegionState for XcRegions {
+		/// Returns whether the metadata of the region can still be challenged.
+		///
+		/// ## Arguments:
+		/// - `raw_region_id` - The `u128` encoded region identifier.
+		#[ink(message)]
+		fn is_pending(&self, id: Id) -> bool {
+			let Id::U128(region_id) = id else { return false };
+			self.pending_regions.contains(region_id)
+		}
+
+		/// A function for checking whether a region is finally assigned to a task.
+		///
+		/// Finally assigned regions no longer exist on the Coretime chain, so they can't be
+		/// transferred or removed.
+		///
+		/// ## Arguments:
+		/// - `raw_region_id` - The `u128` encoded region identifier.
+		#[ink(message)]
+		fn is_finally_assigned(&self, id: Id) -> bool {
+			let Id::U128(region_id) = id else { return false };
+			self.assignments.get(region_id).map(|a| a.finality) == Some(Finality::Final)
 		}
 	}
 
-	// Internal functions:
-	#[cfg(not(test))]
 	impl XcRegions {
-		fn _transfer(&self, region_id: RawRegionId, dest: AccountId) -> Result<(), XcRegionsError> {
-			self.env()
-				.call_runtime(&RuntimeCall::Uniques(UniquesCall::Transfer {
-					collection: REGIONS_COLLECTION_ID,
-					item: region_id,
-					dest: dest.into(),
-				}))
-				.map_err(|_| XcRegionsError::RuntimeError)?;
+		/// A function for minting a wrapped xcRegion and initializing its metadata from a storage
+		/// proof of the Coretime chain state.
+		///
+		/// Unlike `init`, the metadata isn't supplied by the caller. Instead, the region record is
+		/// read from the `Regions` storage of pallet-broker through the provided proof, which gets
+		/// verified against the Coretime chain state root at `state_root_block`. The state root is
+		/// provided by the runtime through a chain extension.
+		///
+		/// ## Arguments:
+		/// - `raw_region_id` - The `u128` encoded region identifier.
+		/// - `state_root_block` - The Coretime chain block number whose state root the proof is
+		///   verified against. Can't be older than the latest known state root by more than
+		///   `max_state_root_age` blocks, since the record of a region changes when it gets
+		///   partitioned.
+		/// - `proof` - The trie nodes proving the region record under the state root.
+		///
+		/// The `end` of the region is taken from the proven record, while the rest of the metadata
+		/// is extracted from the region id. The owner of the record on the Coretime chain isn't
+		/// checked, since the caller must own the region on this chain, just like with `init`.
+		///
+		/// NOTE: Prior to invoking this ink message, the caller must grant approval to the contract
+		/// for the region, enabling its transfer.
+		///
+		/// ## Events:
+		/// On success this ink message emits the `RegionInitialized` event.
+		#[ink(message)]
+		pub fn init_with_proof(
+			&mut self,
+			id: Id,
+			state_root_block: BlockNumber,
+			proof: Vec<Vec<u8>>,
+		) -> Result<(), XcRegionsError> {
+			let caller = self.env().caller();
 
-			Ok(())
-		}
+			let Id::U128(raw_region_id) = id else { return Err(XcRegionsError::InvalidRegionId) };
+			self._ensure_can_initialize(caller, raw_region_id)?;
 
-		/// Returns whether the region exists on this chain or not.
-		fn _uniques_exists(&self, region_id: RawRegionId) -> bool {
-			self._uniques_item(region_id).is_some()
-		}
+			let state_root = self._recent_coretime_state_root(state_root_block)?;
 
-		/// Returns the details of an item within a collection.
-		fn _uniques_item(&self, item_id: RawRegionId) -> Option<ItemDetails> {
-			self.env().extension().item(REGIONS_COLLECTION_ID, item_id).ok()?
-		}
+			let region_id = RegionId::from(raw_region_id);
+			let record = read_region_record(&state_root, &region_id, proof)
+				.map_err(XcRegionsError::InvalidProof)?;
+
+			let region = Region {
+				begin: region_id.begin,
+				end: record.end,
+				core: region_id.core,
+				mask: region_id.mask,
+			};
 
-		/// The owner of the specific item.
-		fn _uniques_owner(&self, region_id: RawRegionId) -> Option<AccountId> {
-			self.env().extension().owner(REGIONS_COLLECTION_ID, region_id).ok()?
+			self._init(caller, raw_region_id, region)
 		}
-	}
 
-	// Implelementation of internal functions used only for integration tests.
-	#[cfg(test)]
-	impl XcRegions {
-		fn _transfer(
-			&mut self,
-			region_id: RawRegionId,
-			dest: AccountId,
-		) -> Result<(), XcRegionsError> {
-			self.burn((REGIONS_COLLECTION_ID, region_id)).unwrap();
-			self.mint((REGIONS_COLLECTION_ID, region_id), dest).unwrap();
-			Ok(())
-		}
+		/// A function for minting a wrapped xcRegion and optimistically initializing its metadata.
+		///
+		/// This works just like `init`, except that the caller has to post a bond, and the
+		/// metadata can be challenged by anyone during the challenge period. Until the challenge
+		/// period is over and the region gets finalized, the region is considered pending and it
+		/// can't be transferred, removed or listed on the market.
+		///
+		/// ## Arguments:
+		/// - `raw_region_id` - The `u128` encoded region identifier.
+		/// - `region` - The corresponding region metadata.
+		///
+		/// This call is payable, since the caller must send exactly the bond specified in the
+		/// optimistic configuration. The bond is returned once the region gets finalized, or
+		/// goes to the challenger if the metadata turns out to be incorrect.
+		///
+		/// NOTE: Prior to invoking this ink message, the caller must grant approval to the contract
+		/// for the region, enabling its transfer.
+		///
+		/// ## Events:
+		/// On success this ink message emits the `RegionInitialized` event.
+		#[ink(message, payable)]
+		pub fn init_optimistic(&mut self, id: Id, region: Region) -> Result<(), XcRegionsError> {
+			let caller = self.env().caller();
 
-		/// Returns whether the region exists on this chain or not.
-		pub fn _uniques_exists(&self, region_id: RawRegionId) -> bool {
-			self._uniques_item(region_id).is_some()
-		}
+			let Id::U128(raw_region_id) = id else { return Err(XcRegionsError::InvalidRegionId) };
+			let config = self.optimistic_config.ok_or(XcRegionsError::OptimisticInitDisabled)?;
 
-		/// Returns the details of an item within a collection.
-		pub fn _uniques_item(&self, item_id: RawRegionId) -> Option<ItemDetails> {
-			self.items.get((REGIONS_COLLECTION_ID, item_id))
-		}
+			ensure!(self.env().transferred_value() == config.bond, XcRegionsError::MissingBond);
 
-		/// The owner of the specific item.
-		pub fn _uniques_owner(&self, region_id: RawRegionId) -> Option<AccountId> {
-			self.items.get((REGIONS_COLLECTION_ID, region_id)).map(|a| a.owner)
+			self._ensure_can_initialize(caller, raw_region_id)?;
+			self._ensure_matching_metadata(raw_region_id, &region)?;
+
+			self._init(caller, raw_region_id, region)?;
+
+			let challenge_deadline =
+				self.env().block_number().saturating_add(config.challenge_period);
+			self.pending_regions.insert(
+				raw_region_id,
+				&PendingRegion { initializer: caller, bond: config.bond, challenge_deadline },
+			);
+
+			Ok(())
 		}
 
-		pub fn mint(
-			&mut self,
-			id: (CollectionId, RawRegionId),
-			owner: AccountId,
-		) -> Result<(), &'static str> {
-			ensure!(self.items.get((id.0, id.1)).is_none(), "Item already exists");
-			self.items.insert(
-				(id.0, id.1),
-				&ItemDetails {
-					owner,
+		/// A function for minting a wrapped xcRegion and initializing its metadata based on the
+		/// attestations of the registered relayers.
+		///
+		/// ## Arguments:
+		/// - `raw_region_id` - The `u128` encoded region identifier.
+		/// - `region` - The corresponding region metadata.
+		/// - `nonce` - The current attestation nonce of the region.
+		/// - `valid_until` - The last block at which the attestations are accepted.
+		/// - `attestations` - The keys of the relayers along with their signatures of the
+		///   SCALE-encoded `(contract, raw_region_id, region, nonce, valid_until)` tuple, where
+		///   `contract` is the account of this contract. Each signature is made with the scheme of
+		///   the relayer's key: sr25519 signatures are made in the `substrate` signing context,
+		///   just like the ones of Substrate accounts.
+		///
+		/// At least `attestation_threshold` distinct registered relayers must have signed the
+		/// metadata. Since ink! 4 only exposes the ecdsa host functions, the signatures are
+		/// verified by the runtime through the signature verifier chain extension. The attestation
+		/// nonce of the region gets incremented on success, so the same attestations can't be used
+		/// again.
+		///
+		/// NOTE: Prior to invoking this ink message, the caller must grant approval to the contract
+		/// for the region, enabling its transfer.
+		///
+		/// ## Events:
+		/// On success this ink message emits the `RegionInitialized` and the `RegionAttested`
+		/// events.
+		#[ink(message)]
+		pub fn init_with_attestations(
+			&mut self,
+			id: Id,
+			region: Region,
+			nonce: AttestationNonce,
+			valid_until: BlockNumber,
+			attestations: Vec<(RelayerKey, Attestation)>,
+		) -> Result<(), XcRegionsError> {
+			let caller = self.env().caller();
+
+			let Id::U128(raw_region_id) = id else { return Err(XcRegionsError::InvalidRegionId) };
+			ensure!(self.attestation_threshold > 0, XcRegionsError::AttestationDisabled);
+			ensure!(self.env().block_number() <= valid_until, XcRegionsError::AttestationExpired);
+
+			self._ensure_can_initialize(caller, raw_region_id)?;
+			self._ensure_matching_metadata(raw_region_id, &region)?;
+
+			ensure!(
+				nonce == self.attestation_nonces.get(raw_region_id).unwrap_or_default(),
+				XcRegionsError::InvalidNonce
+			);
+
+			let message = scale::Encode::encode(&(
+				self.env().account_id(),
+				raw_region_id,
+				&region,
+				nonce,
+				valid_until,
+			));
+
+			let relayers = self.relayers.get_or_default();
+			let mut attestors: Vec<RelayerKey> = Vec::with_capacity(attestations.len());
+			for (attestor, signature) in attestations {
+				ensure!(relayers.contains(&attestor), XcRegionsError::InvalidAttestation);
+				ensure!(!attestors.contains(&attestor), XcRegionsError::InvalidAttestation);
+				ensure!(
+					self._verify_attestation(&attestor, &signature, &message),
+					XcRegionsError::InvalidAttestation
+				);
+
+				attestors.push(attestor);
+			}
+
+			ensure!(
+				attestors.len() >= self.attestation_threshold as usize,
+				XcRegionsError::NotEnoughAttestations
+			);
+
+			self.attestation_nonces.insert(raw_region_id, &nonce.saturating_add(1));
+			self._init(caller, raw_region_id, region)?;
+
+			self.env()
+				.emit_event(RegionAttested { region_id: raw_region_id, attestors, nonce });
+
+			Ok(())
+		}
+	}
+
+	impl XcRegions {
+		/// ## Arguments:
+		/// - `regions_collection_id`: The collection id of the underlying regions on the chain on
+		///   which the contract is deployed.
+		/// - `backend`: The pallet holding the underlying regions, either pallet-uniques or
+		///   pallet-nfts, along with the indices of the pallet and its calls in the runtime of the
+		///   chain on which the contract is deployed.
+		///
+		/// The caller becomes the owner of the contract.
+		#[ink(constructor)]
+		pub fn new(regions_collection_id: CollectionId, backend: Backend) -> Self {
+			let mut instance = Self {
+				regions_collection_id,
+				backend,
+				timeslice_period: TIMESLICE_PERIOD,
+				max_state_root_age: MAX_STATE_ROOT_AGE,
+				..Default::default()
+			};
+			ownable::InternalImpl::_init_with_owner(&mut instance, Self::env().caller());
+
+			let collection_id = Id::U32(regions_collection_id);
+			metadata::InternalImpl::_set_attribute(
+				&mut instance,
+				collection_id.clone(),
+				String::from("name"),
+				String::from(COLLECTION_NAME),
+			);
+			metadata::InternalImpl::_set_attribute(
+				&mut instance,
+				collection_id,
+				String::from("symbol"),
+				String::from(COLLECTION_SYMBOL),
+			);
+
+			instance
+		}
+
+		/// Returns the collection id of the underlying regions.
+		#[ink(message)]
+		pub fn regions_collection_id(&self) -> CollectionId {
+			self.regions_collection_id
+		}
+
+		/// Returns the pallet holding the underlying regions.
+		#[ink(message)]
+		pub fn backend(&self) -> Backend {
+			self.backend
+		}
+
+		/// Returns the configuration of the optimistic initialization.
+		#[ink(message)]
+		pub fn optimistic_config(&self) -> Option<OptimisticConfig> {
+			self.optimistic_config
+		}
+
+		/// A function for updating the configuration of the optimistic initialization.
+		///
+		/// Setting the configuration to `None` disables the optimistic initialization. Already
+		/// pending regions keep their bond and challenge deadline.
+		///
+		/// Only callable by the owner of the contract.
+		#[ink(message)]
+		#[modifiers(only_owner)]
+		pub fn set_optimistic_config(
+			&mut self,
+			config: Option<OptimisticConfig>,
+		) -> Result<(), XcRegionsError> {
+			self.optimistic_config = config;
+			Ok(())
+		}
+
+		/// Returns the configuration for sending regions back to the Coretime chain.
+		#[ink(message)]
+		pub fn teleport_config(&self) -> Option<TeleportConfig> {
+			self.teleport_config
+		}
+
+		/// A function for updating the configuration for sending regions back to the Coretime
+		/// chain.
+		///
+		/// Setting the configuration to `None` disables `remove_and_teleport`.
+		///
+		/// Only callable by the owner of the contract.
+		#[ink(message)]
+		#[modifiers(only_owner)]
+		pub fn set_teleport_config(
+			&mut self,
+			config: Option<TeleportConfig>,
+		) -> Result<(), XcRegionsError> {
+			self.teleport_config = config;
+			Ok(())
+		}
+
+		/// Returns the configuration for dispatching calls on the Coretime chain.
+		#[ink(message)]
+		pub fn transact_config(&self) -> Option<TransactConfig> {
+			self.transact_config
+		}
+
+		/// A function for updating the configuration for dispatching calls on the Coretime chain.
+		///
+		/// Setting the configuration to `None` disables `assign`.
+		///
+		/// Only callable by the owner of the contract.
+		#[ink(message)]
+		#[modifiers(only_owner)]
+		pub fn set_transact_config(
+			&mut self,
+			config: Option<TransactConfig>,
+		) -> Result<(), XcRegionsError> {
+			self.transact_config = config;
+			Ok(())
+		}
+
+		/// Returns the number of relay chain blocks in a timeslice.
+		#[ink(message)]
+		pub fn timeslice_period(&self) -> BlockNumber {
+			self.timeslice_period
+		}
+
+		/// A function for updating the number of relay chain blocks in a timeslice.
+		///
+		/// Only callable by the owner of the contract.
+		#[ink(message)]
+		#[modifiers(only_owner)]
+		pub fn set_timeslice_period(
+			&mut self,
+			timeslice_period: BlockNumber,
+		) -> Result<(), XcRegionsError> {
+			self.timeslice_period = timeslice_period;
+			Ok(())
+		}
+
+		/// Returns the number of blocks by which the state root a storage proof is verified against
+		/// may lag behind the latest known state root.
+		#[ink(message)]
+		pub fn max_state_root_age(&self) -> BlockNumber {
+			self.max_state_root_age
+		}
+
+		/// A function for updating the number of blocks by which the state root a storage proof is
+		/// verified against may lag behind the latest known state root.
+		///
+		/// Only callable by the owner of the contract.
+		#[ink(message)]
+		#[modifiers(only_owner)]
+		pub fn set_max_state_root_age(
+			&mut self,
+			max_state_root_age: BlockNumber,
+		) -> Result<(), XcRegionsError> {
+			self.max_state_root_age = max_state_root_age;
+			Ok(())
+		}
+
+		/// Returns the configuration for reaping expired regions.
+		#[ink(message)]
+		pub fn reap_config(&self) -> Option<ReapConfig> {
+			self.reap_config
+		}
+
+		/// A function for updating the configuration for reaping expired regions.
+		///
+		/// Setting the configuration to `None` disables `reap_expired`.
+		///
+		/// Only callable by the owner of the contract.
+		#[ink(message)]
+		#[modifiers(only_owner)]
+		pub fn set_reap_config(
+			&mut self,
+			config: Option<ReapConfig>,
+		) -> Result<(), XcRegionsError> {
+			self.reap_config = config;
+			Ok(())
+		}
+
+		/// Returns the funds left for rewarding the reaping of expired regions.
+		#[ink(message)]
+		pub fn reap_reward_pool(&self) -> Balance {
+			self.reap_reward_pool
+		}
+
+		/// A function for adding the transferred value to the reap reward pool.
+		#[ink(message, payable)]
+		pub fn fund_reap_reward_pool(&mut self) {
+			self.reap_reward_pool =
+				self.reap_reward_pool.saturating_add(self.env().transferred_value());
+		}
+
+		/// Returns the task to which the region is assigned, if any.
+		#[ink(message)]
+		pub fn assignment(&self, id: Id) -> Option<Assignment> {
+			let Id::U128(region_id) = id else { return None };
+			self.assignments.get(region_id)
+		}
+
+		/// Returns the details of a pending region.
+		#[ink(message)]
+		pub fn pending_region(&self, id: Id) -> Result<PendingRegion, XcRegionsError> {
+			let Id::U128(region_id) = id else { return Err(XcRegionsError::InvalidRegionId) };
+			self.pending_regions.get(region_id).ok_or(XcRegionsError::RegionNotPending)
+		}
+
+		/// A function for challenging the metadata of a pending region.
+		///
+		/// ## Arguments:
+		/// - `raw_region_id` - The `u128` encoded region identifier.
+		/// - `evidence` - Either a storage proof of the region record on the Coretime chain, or the
+		///   verdict of the arbiter. Just like with `init_with_proof`, the state root the proof is
+		///   verified against can't be older than the latest known state root by more than
+		///   `max_state_root_age` blocks.
+		///
+		/// The challenge only succeeds if the evidence shows that the stored `end` of the region
+		/// is incorrect. In that case the metadata gets corrected, its version gets incremented
+		/// and the region is no longer pending. The bond of the initializer goes to the caller.
+		///
+		/// ## Events:
+		/// On success this ink message emits the `RegionChallenged` event.
+		#[ink(message)]
+		pub fn challenge(
+			&mut self,
+			id: Id,
+			evidence: ChallengeEvidence,
+		) -> Result<(), XcRegionsError> {
+			let caller = self.env().caller();
+
+			let Id::U128(region_id) = id else { return Err(XcRegionsError::InvalidRegionId) };
+			let pending =
+				self.pending_regions.get(region_id).ok_or(XcRegionsError::RegionNotPending)?;
+
+			ensure!(
+				self.env().block_number() <= pending.challenge_deadline,
+				XcRegionsError::ChallengePeriodOver
+			);
+
+			let mut region = self.regions.get(region_id).ok_or(XcRegionsError::MetadataNotFound)?;
+
+			let end = match evidence {
+				ChallengeEvidence::Proof { state_root_block, proof } => {
+					let state_root = self._recent_coretime_state_root(state_root_block)?;
+
+					read_region_record(&state_root, &RegionId::from(region_id), proof)
+						.map_err(XcRegionsError::InvalidProof)?
+						.end
+				},
+				ChallengeEvidence::Verdict { end } => {
+					let arbiter = self.optimistic_config.and_then(|config| config.arbiter);
+					ensure!(Some(caller) == arbiter, XcRegionsError::NotArbiter);
+					end
+				},
+			};
+
+			ensure!(end != region.end, XcRegionsError::MetadataNotFraudulent);
+
+			region.end = end;
+			let version = self._store_metadata(region_id, &region, caller);
+			self.pending_regions.remove(region_id);
+
+			self.env()
+				.transfer(caller, pending.bond)
+				.map_err(|_| XcRegionsError::TransferFailed)?;
+
+			self.env().emit_event(RegionChallenged {
+				region_id,
+				challenger: caller,
+				metadata: region,
+				version,
+			});
+
+			Ok(())
+		}
+
+		/// A function for finalizing the metadata of a pending region once its challenge period
+		/// is over. Callable by anyone.
+		///
+		/// The bond is returned to the initializer of the region.
+		///
+		/// ## Arguments:
+		/// - `raw_region_id` - The `u128` encoded region identifier.
+		///
+		/// ## Events:
+		/// On success this ink message emits the `RegionFinalized` event.
+		#[ink(message)]
+		pub fn finalize(&mut self, id: Id) -> Result<(), XcRegionsError> {
+			let Id::U128(region_id) = id else { return Err(XcRegionsError::InvalidRegionId) };
+			let pending =
+				self.pending_regions.get(region_id).ok_or(XcRegionsError::RegionNotPending)?;
+
+			ensure!(
+				self.env().block_number() > pending.challenge_deadline,
+				XcRegionsError::ChallengePeriodNotOver
+			);
+
+			self.pending_regions.remove(region_id);
+
+			self.env()
+				.transfer(pending.initializer, pending.bond)
+				.map_err(|_| XcRegionsError::TransferFailed)?;
+
+			self.env().emit_event(RegionFinalized { region_id });
+
+			Ok(())
+		}
+
+		/// Returns the keys of the registered relayers.
+		#[ink(message)]
+		pub fn relayers(&self) -> Vec<RelayerKey> {
+			self.relayers.get_or_default()
+		}
+
+		/// Returns the number of relayer attestations required for initializing a region.
+		#[ink(message)]
+		pub fn attestation_threshold(&self) -> u32 {
+			self.attestation_threshold
+		}
+
+		/// Returns the nonce the next attestations of the region must include.
+		#[ink(message)]
+		pub fn attestation_nonce(&self, id: Id) -> Result<AttestationNonce, XcRegionsError> {
+			let Id::U128(region_id) = id else { return Err(XcRegionsError::InvalidRegionId) };
+			Ok(self.attestation_nonces.get(region_id).unwrap_or_default())
+		}
+
+		/// A function for registering a relayer.
+		///
+		/// Only callable by the owner of the contract.
+		#[ink(message)]
+		#[modifiers(only_owner)]
+		pub fn add_relayer(&mut self, relayer: RelayerKey) -> Result<(), XcRegionsError> {
+			let mut relayers = self.relayers.get_or_default();
+			ensure!(!relayers.contains(&relayer), XcRegionsError::RelayerAlreadyRegistered);
+
+			relayers.push(relayer);
+			self.relayers.set(&relayers);
+
+			Ok(())
+		}
+
+		/// A function for deregistering a relayer.
+		///
+		/// The number of remaining relayers can't fall below the attestation threshold.
+		///
+		/// Only callable by the owner of the contract.
+		#[ink(message)]
+		#[modifiers(only_owner)]
+		pub fn remove_relayer(&mut self, relayer: RelayerKey) -> Result<(), XcRegionsError> {
+			let mut relayers = self.relayers.get_or_default();
+			let index = relayers
+				.iter()
+				.position(|r| *r == relayer)
+				.ok_or(XcRegionsError::RelayerNotRegistered)?;
+
+			relayers.remove(index);
+			ensure!(
+				relayers.len() >= self.attestation_threshold as usize,
+				XcRegionsError::InvalidThreshold
+			);
+			self.relayers.set(&relayers);
+
+			Ok(())
+		}
+
+		/// A function for updating the number of relayer attestations required for initializing
+		/// a region. Setting it to zero disables the initialization through attestations.
+		///
+		/// Only callable by the owner of the contract.
+		#[ink(message)]
+		#[modifiers(only_owner)]
+		pub fn set_attestation_threshold(&mut self, threshold: u32) -> Result<(), XcRegionsError> {
+			ensure!(
+				threshold as usize <= self.relayers.get_or_default().len(),
+				XcRegionsError::InvalidThreshold
+			);
+			self.attestation_threshold = threshold;
+
+			Ok(())
+		}
+	}
+
+	impl XcRegions {
+		/// A function for querying the regions owned by `owner` along with their metadata.
+		///
+		/// The regions are returned in the order in which they are enumerated by
+		/// `owners_token_by_index`. Since transferring a region changes this order, a region can
+		/// be skipped or returned twice if the regions of the owner change between two pages.
+		///
+		/// At most `MAX_PAGE_SIZE` regions of the owner are scanned per page, so a page can hold
+		/// fewer regions than `limit`, or none at all, even though `next` is set.
+		///
+		/// ## Arguments:
+		/// - `owner` - The account whose regions are returned.
+		/// - `filter` - The criteria the returned regions must match.
+		/// - `cursor` - The index from which the regions of the owner are searched. Zero for the
+		///   first page, otherwise the `next` cursor of the previous page.
+		/// - `limit` - The maximum number of regions returned. Capped at `MAX_PAGE_SIZE`, which is
+		///   also used if `limit` is zero.
+		#[ink(message)]
+		pub fn regions_of(
+			&self,
+			owner: AccountId,
+			filter: RegionFilter,
+			cursor: u128,
+			limit: u32,
+		) -> RegionsPage {
+			let limit = if limit == 0 { MAX_PAGE_SIZE } else { limit.min(MAX_PAGE_SIZE) } as usize;
+			let balance = u128::from(psp34::PSP34Impl::balance_of(self, owner));
+			let scan_end = cursor.saturating_add(u128::from(MAX_PAGE_SIZE)).min(balance);
+			let current_timeslice = self._current_timeslice();
+
+			let mut regions = Vec::new();
+			let mut index = cursor;
+			while index < scan_end && regions.len() < limit {
+				let id = enumerable::PSP34EnumerableImpl::owners_token_by_index(self, owner, index);
+				index = index.saturating_add(1);
+
+				let Ok(id) = id else { continue };
+				let Ok(versioned) = RegionMetadata::get_metadata(self, id.clone()) else {
+					continue
+				};
+				if filter.matches(&versioned.region, current_timeslice) {
+					regions.push((id, versioned));
+				}
+			}
+
+			RegionsPage { regions, next: if index < balance { Some(index) } else { None } }
+		}
+
+		/// A function for querying the metadata history of a region.
+		///
+		/// The history contains all the metadata ever stored for the region, from the oldest to
+		/// the newest, including the metadata of earlier wraps of the region.
+		///
+		/// ## Arguments:
+		/// - `raw_region_id` - The `u128` encoded region identifier.
+		/// - `cursor` - The position in the history from which the entries are returned. Zero for
+		///   the first page, otherwise the `next` cursor of the previous page.
+		/// - `limit` - The maximum number of entries returned. Capped at `MAX_PAGE_SIZE`, which is
+		///   also used if `limit` is zero.
+		#[ink(message)]
+		pub fn metadata_history(
+			&self,
+			id: Id,
+			cursor: u32,
+			limit: u32,
+		) -> Result<MetadataHistoryPage, XcRegionsError> {
+			let Id::U128(region_id) = id else { return Err(XcRegionsError::InvalidRegionId) };
+			let len = self.metadata_history_len.get(region_id).unwrap_or_default();
+			let limit = if limit == 0 { MAX_PAGE_SIZE } else { limit.min(MAX_PAGE_SIZE) };
+			let end = cursor.saturating_add(limit).min(len);
+
+			let records = (cursor..end)
+				.filter_map(|index| self.metadata_history.get((region_id, index)))
+				.collect();
+
+			Ok(MetadataHistoryPage { records, next: if end < len { Some(end) } else { None } })
+		}
+
+		/// Returns an SVG image visualising the region as a data URI, so that it can be displayed
+		/// without an off-chain image service.
+		///
+		/// The image shows the core of the region, its core mask and its timeline relative to the
+		/// current timeslice. See `render::render_svg`.
+		#[ink(message)]
+		pub fn token_uri(&self, id: Id) -> Result<String, XcRegionsError> {
+			let versioned = RegionMetadata::get_metadata(self, id)?;
+			Ok(render::token_uri(&versioned, self._current_timeslice()))
+		}
+	}
+
+	impl XcRegions {
+		/// A function for initializing multiple regions at once.
+		///
+		/// Each region is initialized the same way as through `init`. The batch is applied
+		/// atomically: if any of the regions fails to be initialized, none of them are. In that
+		/// case the error contains the index and the error of each failed item, so all the
+		/// failures can be found with a single dry run.
+		///
+		/// ## Arguments:
+		/// - `regions` - The `u128` encoded identifiers of the regions along with their metadata.
+		///   Can contain at most `MAX_BATCH_SIZE` items.
+		///
+		/// ## Events:
+		/// On success this ink message emits the `RegionInitialized` event for each region.
+		#[ink(message)]
+		pub fn init_batch(&mut self, regions: Vec<(Id, Region)>) -> Result<(), XcRegionsError> {
+			self._apply_batch(regions, |contract, (id, region)| {
+				RegionMetadata::init(contract, id, region)
+			})
+		}
+
+		/// A function for removing multiple regions at once.
+		///
+		/// Each region is removed the same way as through `remove`. The batch is applied
+		/// atomically, see `init_batch`.
+		///
+		/// ## Arguments:
+		/// - `ids` - The `u128` encoded identifiers of the regions. Can contain at most
+		///   `MAX_BATCH_SIZE` items.
+		///
+		/// ## Events:
+		/// On success this ink message emits the `RegionRemoved` event for each region.
+		#[ink(message)]
+		pub fn remove_batch(&mut self, ids: Vec<Id>) -> Result<(), XcRegionsError> {
+			self._apply_batch(ids, RegionMetadata::remove)
+		}
+	}
+
+	impl XcRegions {
+		/// A function for partitioning a wrapped region into two non-overlapping regions at the
+		/// `pivot` timeslice.
+		///
+		/// This mirrors the `partition` extrinsic of pallet-broker: the first region keeps the
+		/// identifier of the partitioned region and ends at `pivot`, while the second region begins
+		/// at `pivot`. Both regions are minted to the caller, who must own the partitioned region.
+		///
+		/// The underlying region stays locked in the contract until the resulting regions are
+		/// merged back, so they can't be removed in the meantime. The lock is bounded by the end
+		/// of the underlying region: the derived regions expire along with it, and once the last of
+		/// them is reaped through `reap_expired`, the underlying region is released.
+		///
+		/// ## Arguments:
+		/// - `raw_region_id` - The `u128` encoded region identifier.
+		/// - `pivot` - The timeslice at which the region is partitioned. Must be within the region.
+		///
+		/// ## Events:
+		/// On success this ink message emits the `RegionPartitioned` event.
+		#[ink(message)]
+		pub fn partition(&mut self, id: Id, pivot: Timeslice) -> Result<(), XcRegionsError> {
+			let caller = self.env().caller();
+
+			let Id::U128(region_id) = id else { return Err(XcRegionsError::InvalidRegionId) };
+			let region = self._ensure_can_split(region_id)?;
+
+			let ((first_id, first), (second_id, second)) =
+				region.partition(pivot).map_err(XcRegionsError::Region)?;
+			let (first_id, second_id) = (RawRegionId::from(first_id), RawRegionId::from(second_id));
+
+			let underlying = self._burn_derived(caller, region_id)?;
+			let first_version = self._mint_derived(caller, first_id, first, &underlying)?;
+			let second_version = self._mint_derived(caller, second_id, second, &underlying)?;
+
+			self.env().emit_event(RegionPartitioned {
+				region_id,
+				pivot,
+				new_regions: ((first_id, first_version), (second_id, second_version)),
+			});
+
+			Ok(())
+		}
+
+		/// A function for interlacing a wrapped region into two regions with complementary masks.
+		///
+		/// This mirrors the `interlace` extrinsic of pallet-broker: the first region gets
+		/// `pivot_mask` as its mask, while the second region gets the remaining parts of the
+		/// original mask. Both regions are minted to the caller, who must own the interlaced
+		/// region.
+		///
+		/// The underlying region stays locked in the contract until the resulting regions are
+		/// merged back, so they can't be removed in the meantime. The lock is bounded by the end
+		/// of the underlying region: the derived regions expire along with it, and once the last of
+		/// them is reaped through `reap_expired`, the underlying region is released.
+		///
+		/// ## Arguments:
+		/// - `raw_region_id` - The `u128` encoded region identifier.
+		/// - `pivot_mask` - The mask of the first region. Must be a non-empty strict subset of the
+		///   region's mask.
+		///
+		/// ## Events:
+		/// On success this ink message emits the `RegionInterlaced` event.
+		#[ink(message)]
+		pub fn interlace(&mut self, id: Id, pivot_mask: CoreMask) -> Result<(), XcRegionsError> {
+			let caller = self.env().caller();
+
+			let Id::U128(region_id) = id else { return Err(XcRegionsError::InvalidRegionId) };
+			let region = self._ensure_can_split(region_id)?;
+
+			let ((first_id, first), (second_id, second)) =
+				region.interlace(pivot_mask).map_err(XcRegionsError::Region)?;
+			let (first_id, second_id) = (RawRegionId::from(first_id), RawRegionId::from(second_id));
+
+			let underlying = self._burn_derived(caller, region_id)?;
+			let first_version = self._mint_derived(caller, first_id, first, &underlying)?;
+			let second_version = self._mint_derived(caller, second_id, second, &underlying)?;
+
+			self.env().emit_event(RegionInterlaced {
+				region_id,
+				pivot_mask,
+				new_regions: ((first_id, first_version), (second_id, second_version)),
+			});
+
+			Ok(())
+		}
+
+		/// A function for merging two wrapped regions into a single one.
+		///
+		/// The regions must either be time-adjacent with the same mask, or span the same period
+		/// with disjoint masks. Since pallet-broker has no way of merging regions, both regions
+		/// must have been derived from the same underlying region. Both regions must be owned by
+		/// the caller, to whom the resulting region is minted.
+		///
+		/// Once all the regions derived from an underlying region are merged back, the resulting
+		/// region is the underlying region itself, and it can be removed again.
+		///
+		/// ## Arguments:
+		/// - `first` - The `u128` encoded identifier of the first region.
+		/// - `second` - The `u128` encoded identifier of the second region.
+		///
+		/// ## Events:
+		/// On success this ink message emits the `RegionsMerged` event.
+		#[ink(message)]
+		pub fn merge(&mut self, first: Id, second: Id) -> Result<(), XcRegionsError> {
+			let caller = self.env().caller();
+
+			let Id::U128(first_id) = first else { return Err(XcRegionsError::InvalidRegionId) };
+			let Id::U128(second_id) = second else { return Err(XcRegionsError::InvalidRegionId) };
+
+			let first = self._ensure_can_split(first_id)?;
+			let second = self._ensure_can_split(second_id)?;
+			let merged = first.merge(&second).map_err(XcRegionsError::Region)?;
+
+			ensure!(
+				self._underlying_region(first_id) == self._underlying_region(second_id),
+				XcRegionsError::DifferentUnderlying
+			);
+
+			let underlying = self._burn_derived(caller, first_id)?;
+			self._burn_derived(caller, second_id)?;
+
+			let new_region_id = RawRegionId::from(merged.id());
+			let version = self._mint_derived(caller, new_region_id, merged.clone(), &underlying)?;
+			if merged == underlying {
+				// The region is no longer derived, so it can be removed.
+				self.underlying_regions.remove(new_region_id);
+				self.derived_pieces.remove(new_region_id);
+			}
+
+			self.env().emit_event(RegionsMerged {
+				region_ids: (first_id, second_id),
+				new_region_id,
+				version,
+			});
+
+			Ok(())
+		}
+	}
+
+	impl XcRegions {
+		/// A function for removing the metadata of a region and sending the underlying region
+		/// back to the Coretime chain.
+		///
+		/// Works like `remove`, except that instead of being returned to the owner on this chain,
+		/// the underlying region is withdrawn through XCM and deposited to the owner's account on
+		/// the Coretime chain, which is the reserve of the regions, as configured through
+		/// `set_teleport_config`.
+		///
+		/// Only the owner of the wrapped region can call this function.
+		///
+		/// The relay chain tokens paying for the execution on the Coretime chain are withdrawn
+		/// from the contract's account along with the region. The caller pays the fee specified in
+		/// the teleport configuration to the contract in the same relay chain token, so prior to
+		/// invoking this ink message, the caller must approve the contract to spend the fee through
+		/// pallet-assets.
+		///
+		/// ## Arguments:
+		/// - `raw_region_id` - The `u128` encoded region identifier.
+		/// - `dest` - The location of the Coretime chain, relative to this chain. Must match the
+		///   Coretime chain set in the teleport configuration.
+		///
+		/// ## Events:
+		/// On success this ink message emits the `RegionTeleported` event.
+		#[ink(message)]
+		pub fn remove_and_teleport(
+			&mut self,
+			id: Id,
+			dest: VersionedMultiLocation,
+		) -> Result<(), XcRegionsError> {
+			let Id::U128(region_id) = id else { return Err(XcRegionsError::InvalidRegionId) };
+			let config = self.teleport_config.ok_or(XcRegionsError::TeleportDisabled)?;
+			let reserve = MultiLocation::try_from(dest.clone())
+				.map_err(|_| XcRegionsError::InvalidDestination)?;
+			ensure!(
+				reserve == MultiLocation::new(1, X1(Parachain(config.coretime_para_id))),
+				XcRegionsError::InvalidDestination
+			);
+
+			// The fee is collected first, so that the region isn't removed if it can't be paid.
+			let owner = self._ensure_can_remove(region_id)?;
+			self._collect_fee(owner, config.fee, &config.fee_asset)?;
+			self._remove(region_id)?;
+			self._reserve_withdraw(region_id, reserve, owner, &config)?;
+
+			self.env().emit_event(RegionTeleported { region_id, owner, dest });
+			Ok(())
+		}
+	}
+
+	impl XcRegions {
+		/// A function for assigning a region to a task on the Coretime chain.
+		///
+		/// The `assign` call of pallet-broker is dispatched on the Coretime chain through XCM
+		/// `Transact`, using the configuration set through `set_transact_config`. A provisionally
+		/// assigned region can be reassigned, while a finally assigned region becomes
+		/// non-transferable, since it no longer exists on the Coretime chain.
+		///
+		/// On the Coretime chain, the regions held by the contract are owned by the sovereign
+		/// account of this chain, while the call is dispatched from the account of the contract.
+		/// Therefore, the underlying region is first moved to the account of the contract there,
+		/// after which the wrapped region can't be removed until the underlying region is sent
+		/// back through `retrieve_from_coretime`.
+		///
+		/// The caller pays the fee specified in the transact configuration for each XCM sent, in
+		/// the same way as for `remove_and_teleport`. A region can only be reassigned once
+		/// `reassignment_delay` blocks passed since its last assignment was requested.
+		///
+		/// The outcome of the call isn't known when sending it, and contracts can't receive XCM
+		/// responses, so the assignment is recorded as unconfirmed. The owner of the contract
+		/// confirms it through `confirm_assignment` once its outcome on the Coretime chain is
+		/// known, so the recorded assignments are only as reliable as the owner of the contract.
+		///
+		/// Regions derived within the contract can't be assigned, since pallet-broker only knows
+		/// of their underlying region.
+		///
+		/// Only the owner of the wrapped region can call this function.
+		///
+		/// ## Arguments:
+		/// - `raw_region_id` - The `u128` encoded region identifier.
+		/// - `task` - The parachain to which the region is assigned.
+		/// - `finality` - Whether the assignment can be changed afterwards.
+		///
+		/// ## Events:
+		/// On success this ink message emits the `AssignmentRequested` event.
+		#[ink(message)]
+		pub fn assign(
+			&mut self,
+			id: Id,
+			task: ParaId,
+			finality: Finality,
+		) -> Result<(), XcRegionsError> {
+			let Id::U128(region_id) = id else { return Err(XcRegionsError::InvalidRegionId) };
+			let config = self.transact_config.ok_or(XcRegionsError::TransactDisabled)?;
+
+			let owner = psp34::PSP34Impl::owner_of(self, id.clone())
+				.ok_or(XcRegionsError::MetadataNotFound)?;
+			ensure!(owner == self.env().caller(), XcRegionsError::NotOwner);
+			ensure!(!self.pending_regions.contains(region_id), XcRegionsError::RegionPending);
+			ensure!(!self.underlying_regions.contains(region_id), XcRegionsError::DerivedRegion);
+			ensure!(!self.pooled_regions.contains(region_id), XcRegionsError::RegionPooled);
+
+			let now = self.env().block_number();
+			if let Some(assignment) = self.assignments.get(region_id) {
+				ensure!(assignment.finality != Finality::Final, XcRegionsError::RegionAssigned);
+				ensure!(
+					now >= assignment.requested_at.saturating_add(config.reassignment_delay),
+					XcRegionsError::ReassignmentTooSoon
+				);
+			}
+
+			self._collect_transact_fee(owner, region_id, &config)?;
+			self._move_to_coretime(region_id, &config)?;
+			let call = BrokerCall::Assign { region_id: RegionId::from(region_id), task, finality };
+			self._transact_on_coretime(RuntimeCall::Broker(config.broker_indices, call), &config)?;
+
+			let assignment = Assignment { task, finality, confirmed: false, requested_at: now };
+			self.assignments.insert(region_id, &assignment);
+			self.env().emit_event(AssignmentRequested { region_id, task, finality });
+
+			Ok(())
+		}
+
+		/// A function for confirming the outcome of an assignment on the Coretime chain.
+		///
+		/// If the assignment succeeded, it gets marked as confirmed. Otherwise it is removed,
+		/// leaving the region unassigned. The underlying region stays with the account of the
+		/// contract on the Coretime chain either way, from where it can be sent back through
+		/// `retrieve_from_coretime` unless it got finally assigned.
+		///
+		/// The contract can't observe the Coretime chain, so the owner of the contract is trusted
+		/// to report the outcome truthfully. Wrongly confirming a failed final assignment makes the
+		/// wrapped region non-transferable, while wrongly reporting a successful one as failed
+		/// makes a region that no longer exists on the Coretime chain transferable again.
+		///
+		/// Only callable by the owner of the contract.
+		///
+		/// ## Arguments:
+		/// - `raw_region_id` - The `u128` encoded region identifier.
+		/// - `succeeded` - Whether the `assign` call succeeded on the Coretime chain.
+		///
+		/// ## Events:
+		/// On success this ink message emits the `RegionAssigned` event if the assignment
+		/// succeeded.
+		#[ink(message)]
+		#[modifiers(only_owner)]
+		pub fn confirm_assignment(
+			&mut self,
+			id: Id,
+			succeeded: bool,
+		) -> Result<(), XcRegionsError> {
+			let Id::U128(region_id) = id else { return Err(XcRegionsError::InvalidRegionId) };
+
+			let mut assignment = self
+				.assignments
+				.get(region_id)
+				.filter(|assignment| !assignment.confirmed)
+				.ok_or(XcRegionsError::AssignmentNotFound)?;
+
+			if succeeded {
+				assignment.confirmed = true;
+				self.assignments.insert(region_id, &assignment);
+				self.env().emit_event(RegionAssigned {
+					region_id,
+					task: assignment.task,
+					finality: assignment.finality,
+				});
+			} else {
+				self.assignments.remove(region_id);
+			}
+
+			Ok(())
+		}
+
+		/// A function for sending the underlying region back from the account of the contract on
+		/// the Coretime chain.
+		///
+		/// The `execute` call of pallet-xcm is dispatched on the Coretime chain through XCM
+		/// `Transact`, which sends the region back to the contract on this chain. Once the region
+		/// arrives, the wrapped region can be removed again. A provisional assignment of the
+		/// region stays in effect on the Coretime chain until the region gets reassigned there.
+		///
+		/// The caller pays the fee specified in the transact configuration twice, once for each
+		/// of the chains executing the XCM.
+		///
+		/// Finally assigned and pooled regions no longer exist on the Coretime chain, so they
+		/// can't be sent back.
+		///
+		/// Only the owner of the wrapped region can call this function.
+		///
+		/// ## Arguments:
+		/// - `raw_region_id` - The `u128` encoded region identifier.
+		///
+		/// ## Events:
+		/// On success this ink message emits the `RetrievalRequested` event.
+		#[ink(message)]
+		pub fn retrieve_from_coretime(&mut self, id: Id) -> Result<(), XcRegionsError> {
+			let Id::U128(region_id) = id else { return Err(XcRegionsError::InvalidRegionId) };
+			let config = self.transact_config.ok_or(XcRegionsError::TransactDisabled)?;
+
+			let owner = psp34::PSP34Impl::owner_of(self, id.clone())
+				.ok_or(XcRegionsError::MetadataNotFound)?;
+			ensure!(owner == self.env().caller(), XcRegionsError::NotOwner);
+			ensure!(!self.pooled_regions.contains(region_id), XcRegionsError::RegionPooled);
+			let finality = self.assignments.get(region_id).map(|a| a.finality);
+			ensure!(finality != Some(Finality::Final), XcRegionsError::RegionAssigned);
+			ensure!(self._is_on_coretime(region_id), XcRegionsError::RegionNotOnCoretime);
+
+			// Besides the fee of the XCM sent to the Coretime chain, the caller pays the fee sent
+			// along with the region for the execution on this chain.
+			self._collect_fee(owner, config.fee.saturating_mul(2), &config.fee_asset)?;
+			let message = reserve_transfer_region(
+				config.broker_indices.pallet,
+				region_id,
+				config.fee,
+				config.para_id,
+				&self.env().account_id(),
+			);
+			let (ref_time, proof_size) = config.max_weight;
+			let call = XcmCall::Execute {
+				message: VersionedXcm::V3(message),
+				max_weight: Weight::from_parts(ref_time, proof_size),
+			};
+			self._transact_on_coretime(
+				RuntimeCall::Xcm(config.coretime_xcm_indices, call),
+				&config,
+			)?;
+
+			self.env().emit_event(RetrievalRequested { region_id });
+
+			Ok(())
+		}
+
+		/// A function for contributing a region to the instantaneous coretime pool.
+		///
+		/// The `pool` call of pallet-broker is dispatched on the Coretime chain through XCM
+		/// `Transact`, with final finality. The revenue the region earns is paid to the owner of
+		/// the wrapped region, i.e. to the account with the same id on the Coretime chain, once
+		/// claimed through `claim_revenue`. Since the region no longer exists on the Coretime
+		/// chain, the wrapped region becomes non-transferable until all its revenue is claimed, so
+		/// the payee remains its owner. As with `assign`, the underlying region is first moved to
+		/// the account of the contract on the Coretime chain, and the caller pays the fee
+		/// for each XCM sent.
+		///
+		/// Regions derived within the contract can't be pooled, since pallet-broker only knows of
+		/// their underlying region.
+		///
+		/// Only the owner of the wrapped region can call this function. Regions owned by a contract
+		/// can't be pooled, since the contract couldn't access the revenue on the Coretime chain.
+		///
+		/// ## Arguments:
+		/// - `raw_region_id` - The `u128` encoded region identifier.
+		///
+		/// ## Events:
+		/// On success this ink message emits the `RegionPooled` event.
+		#[ink(message)]
+		pub fn pool(&mut self, id: Id) -> Result<(), XcRegionsError> {
+			let Id::U128(region_id) = id else { return Err(XcRegionsError::InvalidRegionId) };
+			let config = self.transact_config.ok_or(XcRegionsError::TransactDisabled)?;
+
+			let owner = psp34::PSP34Impl::owner_of(self, id.clone())
+				.ok_or(XcRegionsError::MetadataNotFound)?;
+			ensure!(owner == self.env().caller(), XcRegionsError::NotOwner);
+			ensure!(!self.pending_regions.contains(region_id), XcRegionsError::RegionPending);
+			ensure!(!self.underlying_regions.contains(region_id), XcRegionsError::DerivedRegion);
+			ensure!(!self.pooled_regions.contains(region_id), XcRegionsError::RegionPooled);
+			// The revenue is paid to the same account on the Coretime chain, which a contract
+			// can't control.
+			ensure!(!self.env().is_contract(&owner), XcRegionsError::OwnedByContract);
+
+			// A provisional assignment gets replaced by the contribution to the pool.
+			let assignment = self.assignments.get(region_id);
+			ensure!(
+				assignment.map(|a| a.finality) != Some(Finality::Final),
+				XcRegionsError::RegionAssigned
+			);
+
+			let region = self.regions.get(region_id).ok_or(XcRegionsError::MetadataNotFound)?;
+			self._collect_transact_fee(owner, region_id, &config)?;
+			self._move_to_coretime(region_id, &config)?;
+			// Pooled regions can't change hands, so the owner stays the payee until all the
+			// revenue is claimed.
+			let payee = owner;
+			let call = BrokerCall::Pool {
+				region_id: RegionId::from(region_id),
+				payee,
+				finality: Finality::Final,
+			};
+			self._transact_on_coretime(RuntimeCall::Broker(config.broker_indices, call), &config)?;
+
+			self.assignments.remove(region_id);
+			let pooled = PooledRegion { payee, claimed_until: region.begin, pending_claim: None };
+			self.pooled_regions.insert(region_id, &pooled);
+			self.env().emit_event(RegionPooled { region_id, payee });
+
+			Ok(())
+		}
+
+		/// A function for claiming the revenue a pooled region earned in the instantaneous
+		/// coretime pool.
+		///
+		/// The `claim_revenue` call of pallet-broker is dispatched on the Coretime chain through
+		/// XCM `Transact`, which pays the revenue of the next `max_timeslices` timeslices to the
+		/// owner of the wrapped region. The revenue of a timeslice only becomes claimable
+		/// once the timeslice is over, so the claim never reaches beyond the current timeslice.
+		///
+		/// The revenue is paid to the owner on the Coretime chain directly, rather than being
+		/// forwarded by the contract. Since pooled regions can't change hands, the owner is always
+		/// the payee set when pooling the region.
+		///
+		/// Contracts can't receive XCM responses, so the claim is recorded as pending until the
+		/// owner of the contract confirms how far it got through `confirm_revenue_claim`. Only one
+		/// claim of a region can be pending at a time. The caller pays the fee specified in the
+		/// transact configuration.
+		///
+		/// Only the owner of the wrapped region can call this function.
+		///
+		/// ## Arguments:
+		/// - `raw_region_id` - The `u128` encoded region identifier.
+		/// - `max_timeslices` - The maximum number of timeslices whose revenue is claimed.
+		///
+		/// ## Events:
+		/// On success this ink message emits the `RevenueClaimRequested` event.
+		#[ink(message)]
+		pub fn claim_revenue(
+			&mut self,
+			id: Id,
+			max_timeslices: Timeslice,
+		) -> Result<(), XcRegionsError> {
+			let Id::U128(region_id) = id else { return Err(XcRegionsError::InvalidRegionId) };
+			let config = self.transact_config.ok_or(XcRegionsError::TransactDisabled)?;
+
+			let owner = psp34::PSP34Impl::owner_of(self, id.clone())
+				.ok_or(XcRegionsError::MetadataNotFound)?;
+			ensure!(owner == self.env().caller(), XcRegionsError::NotOwner);
+
+			let mut pooled =
+				self.pooled_regions.get(region_id).ok_or(XcRegionsError::RegionNotPooled)?;
+			ensure!(pooled.pending_claim.is_none(), XcRegionsError::ClaimPending);
+			let region = self.regions.get(region_id).ok_or(XcRegionsError::MetadataNotFound)?;
+
+			let from = pooled.claimed_until;
+			let to = from
+				.saturating_add(max_timeslices)
+				.min(region.end)
+				.min(self._current_timeslice());
+			ensure!(to > from, XcRegionsError::NoRevenueToClaim);
+
+			self._collect_fee(owner, config.fee, &config.fee_asset)?;
+
+			// pallet-broker re-keys the contribution each time its revenue is partially claimed.
+			let contribution_id = RegionId { begin: from, core: region.core, mask: region.mask };
+			let call =
+				BrokerCall::ClaimRevenue { region_id: contribution_id, max_timeslices: to - from };
+			self._transact_on_coretime(RuntimeCall::Broker(config.broker_indices, call), &config)?;
+
+			pooled.pending_claim = Some(to);
+			self.pooled_regions.insert(region_id, &pooled);
+			self.env()
+				.emit_event(RevenueClaimRequested { region_id, timeslices: (from, to) });
+
+			Ok(())
+		}
+
+		/// A function for confirming the outcome of a revenue claim on the Coretime chain.
+		///
+		/// pallet-broker stops claiming at the first timeslice whose revenue isn't known yet, and
+		/// re-keys the contribution of the region to begin at that timeslice. The outcome of the
+		/// claim therefore isn't known when sending it, so the next claim can only be made once
+		/// the timeslice the claim reached is confirmed.
+		///
+		/// Once all the revenue is claimed, the wrapped region is burned, since the region no
+		/// longer exists on the Coretime chain.
+		///
+		/// As with `confirm_assignment`, the owner of the contract is trusted to report the
+		/// outcome truthfully.
+		///
+		/// Only callable by the owner of the contract.
+		///
+		/// ## Arguments:
+		/// - `raw_region_id` - The `u128` encoded region identifier.
+		/// - `claimed_until` - The first timeslice whose revenue didn't get claimed, i.e. the
+		///   timeslice at which the remaining contribution begins, or the end of the region if no
+		///   contribution remains. Must be within the timeslices of the pending claim.
+		///
+		/// ## Events:
+		/// On success this ink message emits the `RevenueClaimed` event if any revenue got
+		/// claimed, followed by the `RegionRemoved` event once all the revenue is claimed.
+		#[ink(message)]
+		#[modifiers(only_owner)]
+		pub fn confirm_revenue_claim(
+			&mut self,
+			id: Id,
+			claimed_until: Timeslice,
+		) -> Result<(), XcRegionsError> {
+			let Id::U128(region_id) = id else { return Err(XcRegionsError::InvalidRegionId) };
+
+			let mut pooled =
+				self.pooled_regions.get(region_id).ok_or(XcRegionsError::RegionNotPooled)?;
+			let to = pooled.pending_claim.ok_or(XcRegionsError::ClaimNotFound)?;
+			let from = pooled.claimed_until;
+			ensure!(from <= claimed_until && claimed_until <= to, XcRegionsError::InvalidClaim);
+
+			if claimed_until > from {
+				self.env()
+					.emit_event(RevenueClaimed { region_id, timeslices: (from, claimed_until) });
+			}
+
+			let region = self.regions.get(region_id).ok_or(XcRegionsError::MetadataNotFound)?;
+			if claimed_until < region.end {
+				pooled.claimed_until = claimed_until;
+				pooled.pending_claim = None;
+				self.pooled_regions.insert(region_id, &pooled);
+				return Ok(())
+			}
+
+			// All the revenue is claimed, so there's nothing left that the region represents.
+			let owner = psp34::PSP34Impl::owner_of(self, id.clone())
+				.ok_or(XcRegionsError::MetadataNotFound)?;
+			psp34::InternalImpl::_burn_from(self, owner, id).map_err(XcRegionsError::Psp34)?;
+			self.pooled_regions.remove(region_id);
+			self.regions.remove(region_id);
+			self.regions_on_coretime.remove(region_id);
+
+			self.env().emit_event(RegionRemoved { region_id });
+
+			Ok(())
+		}
+	}
+
+	impl XcRegions {
+		/// A function for cleaning up wrapped regions which already expired.
+		///
+		/// Callable by anyone. For each region, the wrapped region is burned and its metadata is
+		/// removed, while its metadata version is kept for the case it gets re-initialized. The
+		/// underlying region is returned to the owner of the wrapped region. If the region was
+		/// derived within the contract, the underlying region is only returned once the last
+		/// region derived from it is reaped.
+		///
+		/// Pending regions can't be reaped until they get finalized, and pooled regions can't be
+		/// reaped until all their revenue is claimed. Regions owned by a contract, e.g. regions
+		/// listed on the market, can't be reaped either, since the owning contract might not be
+		/// able to handle the underlying region. These must be reclaimed by their owner first.
+		///
+		/// The batch is applied atomically, see `init_batch`. On success, the caller receives the
+		/// configured reward for each reaped region, as long as the reap reward pool holds enough
+		/// funds.
+		///
+		/// ## Arguments:
+		/// - `ids` - The `u128` encoded identifiers of the expired regions. Can contain at most
+		///   `MAX_BATCH_SIZE` items.
+		///
+		/// ## Events:
+		/// On success this ink message emits the `RegionReaped` event for each region.
+		#[ink(message)]
+		pub fn reap_expired(&mut self, ids: Vec<Id>) -> Result<(), XcRegionsError> {
+			let config = self.reap_config.ok_or(XcRegionsError::ReapDisabled)?;
+
+			let reaped = ids.len() as Balance;
+			self._apply_batch(ids, Self::_reap)?;
+
+			let reward = config.reward.saturating_mul(reaped).min(self.reap_reward_pool);
+			if reward > 0 {
+				self.reap_reward_pool = self.reap_reward_pool.saturating_sub(reward);
+				self.env()
+					.transfer(self.env().caller(), reward)
+					.map_err(|_| XcRegionsError::TransferFailed)?;
+			}
+
+			Ok(())
+		}
+	}
+
+	impl XcRegions {
+		/// A function for cancelling the approval of an underlying region held by the contract.
+		///
+		/// Transferring a region to the contract clears its approvals. However, the admin of the
+		/// collection can still approve a delegate afterwards, which could then take the region
+		/// out of the contract while it is wrapped. Callable by anyone.
+		///
+		/// ## Arguments:
+		/// - `raw_region_id` - The `u128` encoded region identifier.
+		#[ink(message)]
+		pub fn cancel_lingering_approval(&mut self, id: Id) -> Result<(), XcRegionsError> {
+			let Id::U128(region_id) = id else { return Err(XcRegionsError::InvalidRegionId) };
+
+			let item = self._uniques_item(region_id).ok_or(XcRegionsError::RegionNotHeld)?;
+			ensure!(item.owner == self.env().account_id(), XcRegionsError::RegionNotHeld);
+			let delegate = item.approved.ok_or(XcRegionsError::NoApproval)?;
+
+			self._cancel_approval(region_id, delegate)
+		}
+	}
+
+	// Internal functions shared by all the ways of initializing a region:
+	impl XcRegions {
+		/// Ensures that the region exists on this chain, is owned by `caller` and doesn't have
+		/// any metadata stored yet.
+		///
+		/// Also ensures that the contract can transfer the region, so that initialization doesn't
+		/// fail with an opaque `RuntimeError` when dispatching the transfer.
+		fn _ensure_can_initialize(
+			&self,
+			caller: AccountId,
+			raw_region_id: RawRegionId,
+		) -> Result<(), XcRegionsError> {
+			let item = self._uniques_item(raw_region_id).ok_or(XcRegionsError::CannotInitialize)?;
+			ensure!(item.owner == caller, XcRegionsError::CannotInitialize);
+
+			// Cannot initialize a region that already has metadata stored.
+			ensure!(self.regions.get(raw_region_id).is_none(), XcRegionsError::CannotInitialize);
+
+			ensure!(!item.is_frozen, XcRegionsError::ItemFrozen);
+			ensure!(!self._uniques_collection_frozen(), XcRegionsError::CollectionFrozen);
+			ensure!(
+				item.approved == Some(self.env().account_id()),
+				XcRegionsError::MissingApproval
+			);
+
+			Ok(())
+		}
+
+		/// Does a sanity check to ensure that the provided region metadata matches with the
+		/// metadata extracted from the region id.
+		fn _ensure_matching_metadata(
+			&self,
+			raw_region_id: RawRegionId,
+			region: &Region,
+		) -> Result<(), XcRegionsError> {
+			let region_id = RegionId::from(raw_region_id);
+			ensure!(region_id.begin == region.begin, XcRegionsError::InvalidMetadata);
+			ensure!(region_id.core == region.core, XcRegionsError::InvalidMetadata);
+			ensure!(region_id.mask == region.mask, XcRegionsError::InvalidMetadata);
+
+			Ok(())
+		}
+
+		/// Returns the state root of the Coretime chain at `block`, ensuring that it isn't older
+		/// than the latest known state root by more than `max_state_root_age` blocks.
+		fn _recent_coretime_state_root(&self, block: BlockNumber) -> Result<Hash, XcRegionsError> {
+			ensure!(
+				block.saturating_add(self.max_state_root_age) >= self._latest_coretime_block(),
+				XcRegionsError::StateRootTooOld
+			);
+
+			self._coretime_state_root(block).ok_or(XcRegionsError::StateRootNotFound)
+		}
+
+		/// Transfers the region to the contract, stores its metadata and mints a wrapped xcRegion
+		/// token for `caller`.
+		fn _init(
+			&mut self,
+			caller: AccountId,
+			raw_region_id: RawRegionId,
+			region: Region,
+		) -> Result<(), XcRegionsError> {
+			let contract = self.env().account_id();
+			self._transfer(raw_region_id, contract)?;
+
+			let new_version = self._store_metadata(raw_region_id, &region, caller);
+
+			psp34::InternalImpl::_mint_to(self, caller, Id::U128(raw_region_id))
+				.map_err(XcRegionsError::Psp34)?;
+
+			self.env().emit_event(RegionInitialized {
+				region_id: raw_region_id,
+				metadata: region,
+				version: new_version,
+			});
+
+			Ok(())
+		}
+
+		/// Stores the metadata of the region under a new version, appends it to the metadata
+		/// history and returns the new version.
+		///
+		/// The first version of a region is zero.
+		fn _store_metadata(
+			&mut self,
+			raw_region_id: RawRegionId,
+			region: &Region,
+			initializer: AccountId,
+		) -> Version {
+			let new_version = if let Some(version) = self.metadata_versions.get(raw_region_id) {
+				version.saturating_add(1)
+			} else {
+				Default::default()
+			};
+
+			self.metadata_versions.insert(raw_region_id, &new_version);
+			self.regions.insert(raw_region_id, region);
+
+			let len = self.metadata_history_len.get(raw_region_id).unwrap_or_default();
+			let record = MetadataRecord {
+				version: new_version,
+				region: region.clone(),
+				initializer,
+				block: self.env().block_number(),
+			};
+			self.metadata_history.insert((raw_region_id, len), &record);
+			self.metadata_history_len.insert(raw_region_id, &len.saturating_add(1));
+
+			new_version
+		}
+	}
+
+	// Internal functions for applying batches:
+	impl XcRegions {
+		/// Applies `f` to each item of the batch, collecting the errors of the failed items.
+		///
+		/// Returning an error reverts the changes made by the successful items.
+		fn _apply_batch<T>(
+			&mut self,
+			items: Vec<T>,
+			f: fn(&mut Self, T) -> Result<(), XcRegionsError>,
+		) -> Result<(), XcRegionsError> {
+			ensure!(items.len() <= MAX_BATCH_SIZE, XcRegionsError::BatchTooLarge);
+
+			let mut errors = Vec::new();
+			for (index, item) in items.into_iter().enumerate() {
+				if let Err(error) = f(self, item) {
+					errors.push((index as u32, error));
+				}
+			}
+
+			ensure!(errors.is_empty(), XcRegionsError::BatchFailed(errors));
+			Ok(())
+		}
+	}
+
+	// Internal functions shared by all the ways of removing a region:
+	impl XcRegions {
+		/// Burns the wrapped region of the caller and removes its metadata, returning the owner.
+		///
+		/// The underlying region is left in the contract for the caller to return it.
+		fn _remove(&mut self, region_id: RawRegionId) -> Result<AccountId, XcRegionsError> {
+			let owner = self._ensure_can_remove(region_id)?;
+
+			psp34::InternalImpl::_burn_from(self, owner, Id::U128(region_id))
+				.map_err(XcRegionsError::Psp34)?;
+			self.regions.remove(region_id);
+			self.assignments.remove(region_id);
+			// The underlying region might have been sent back from the Coretime chain.
+			self.regions_on_coretime.remove(region_id);
+
+			Ok(owner)
+		}
+
+		/// Ensures that the caller owns the wrapped region and that its underlying region can be
+		/// returned, and returns the owner.
+		fn _ensure_can_remove(&self, region_id: RawRegionId) -> Result<AccountId, XcRegionsError> {
+			let owner = psp34::PSP34Impl::owner_of(self, Id::U128(region_id))
+				.ok_or(XcRegionsError::CannotRemove)?;
+
+			ensure!(owner == self.env().caller(), XcRegionsError::CannotRemove);
+			// Removing a pending region would take its metadata out of reach of challengers, so it
+			// must be finalized first.
+			ensure!(!self.pending_regions.contains(region_id), XcRegionsError::RegionPending);
+			ensure!(!self.underlying_regions.contains(region_id), XcRegionsError::DerivedRegion);
+			ensure!(!self.pooled_regions.contains(region_id), XcRegionsError::RegionPooled);
+			// Finally assigned regions no longer exist on the Coretime chain.
+			let finality = self.assignments.get(region_id).map(|a| a.finality);
+			ensure!(finality != Some(Finality::Final), XcRegionsError::RegionAssigned);
+			ensure!(!self._is_on_coretime(region_id), XcRegionsError::RegionOnCoretime);
+
+			Ok(owner)
+		}
+	}
+
+	// Internal functions for moving regions off this chain:
+	impl XcRegions {
+		/// Withdraws the region from the contract and sends it back to its `reserve`, where it
+		/// gets deposited to `beneficiary`.
+		fn _reserve_withdraw(
+			&mut self,
+			region_id: RawRegionId,
+			reserve: MultiLocation,
+			beneficiary: AccountId,
+			config: &TeleportConfig,
+		) -> Result<(), XcRegionsError> {
+			let message = reserve_withdraw_region(
+				reserve,
+				config.broker_pallet_index,
+				region_id,
+				config.fee,
+				&beneficiary,
+			)
+			.ok_or(XcRegionsError::InvalidDestination)?;
+
+			#[cfg(test)]
+			self._record_region_xcm(region_id, &message);
+			self._execute_region_xcm(message, config.indices, config.max_weight)
+		}
+
+		/// Moves the region from the sovereign account of this chain on the Coretime chain to the
+		/// account of the contract there, so that calls on the region can be dispatched from the
+		/// contract. Does nothing if the region was already moved.
+		fn _move_to_coretime(
+			&mut self,
+			region_id: RawRegionId,
+			config: &TransactConfig,
+		) -> Result<(), XcRegionsError> {
+			if self._is_on_coretime(region_id) {
+				return Ok(())
+			}
+
+			let message = reserve_withdraw_region_to(
+				MultiLocation::new(1, X1(Parachain(config.coretime_para_id))),
+				config.broker_indices.pallet,
+				region_id,
+				config.fee,
+				sibling_account(config.para_id, &self.env().account_id()),
+			)
+			.ok_or(XcRegionsError::InvalidDestination)?;
+
+			#[cfg(test)]
+			self._record_region_xcm(region_id, &message);
+			self._execute_region_xcm(message, config.xcm_indices, config.max_weight)?;
+			self.regions_on_coretime.insert(region_id, &());
+
+			Ok(())
+		}
+
+		/// Collects the fee of dispatching a call on the region on the Coretime chain from `payer`,
+		/// including the fee of moving the region there if it wasn't moved yet.
+		fn _collect_transact_fee(
+			&mut self,
+			payer: AccountId,
+			region_id: RawRegionId,
+			config: &TransactConfig,
+		) -> Result<(), XcRegionsError> {
+			let messages = if self._is_on_coretime(region_id) { 1 } else { 2 };
+			self._collect_fee(payer, config.fee.saturating_mul(messages), &config.fee_asset)
+		}
+
+		/// Returns whether the underlying region is with the account of the contract on the
+		/// Coretime chain.
+		///
+		/// Once a region sent back through `retrieve_from_coretime` arrives, it is held by the
+		/// contract on this chain again, even though it is still recorded as moved.
+		fn _is_on_coretime(&self, region_id: RawRegionId) -> bool {
+			self.regions_on_coretime.contains(region_id) &&
+				self._uniques_owner(region_id) != Some(self.env().account_id())
+		}
+	}
+
+	// Internal functions for reaping expired regions:
+	impl XcRegions {
+		/// Burns the expired wrapped region, removes its metadata and releases the underlying
+		/// region.
+		fn _reap(&mut self, id: Id) -> Result<(), XcRegionsError> {
+			let Id::U128(region_id) = id else { return Err(XcRegionsError::InvalidRegionId) };
+			let region = self.regions.get(region_id).ok_or(XcRegionsError::MetadataNotFound)?;
+			let owner = psp34::PSP34Impl::owner_of(self, id.clone())
+				.ok_or(XcRegionsError::MetadataNotFound)?;
+
+			ensure!(region.end <= self._current_timeslice(), XcRegionsError::RegionNotExpired);
+			// The metadata of pending regions isn't trusted yet, so they must be finalized first.
+			ensure!(!self.pending_regions.contains(region_id), XcRegionsError::RegionPending);
+			ensure!(!self.pooled_regions.contains(region_id), XcRegionsError::RegionPooled);
+			ensure!(!self.env().is_contract(&owner), XcRegionsError::OwnedByContract);
+
+			psp34::InternalImpl::_burn_from(self, owner, id).map_err(XcRegionsError::Psp34)?;
+			self.regions.remove(region_id);
+			self.assignments.remove(region_id);
+
+			// Other regions derived from the same underlying region may still be wrapped, so the
+			// underlying region is only released once the last region derived from it is reaped.
+			if let Some(underlying) = self.underlying_regions.get(region_id) {
+				self.underlying_regions.remove(region_id);
+				if self._remove_derived_piece(&underlying) == 0 {
+					self._transfer(RawRegionId::from(underlying.id()), owner)?;
+				}
+			} else if self._is_on_coretime(region_id) {
+				// The underlying region was moved to the Coretime chain, where it expired as well.
+				self.regions_on_coretime.remove(region_id);
+			} else {
+				// The underlying region might have been sent back from the Coretime chain.
+				self.regions_on_coretime.remove(region_id);
+				self._transfer(region_id, owner)?;
+			}
+
+			self.env().emit_event(RegionReaped { region_id, reaper: self.env().caller() });
+			Ok(())
+		}
+	}
+
+	// Internal functions for splitting and merging wrapped regions:
+	impl XcRegions {
+		/// Ensures that the caller owns the wrapped region and that its metadata is final, and
+		/// returns its metadata.
+		fn _ensure_can_split(&self, region_id: RawRegionId) -> Result<Region, XcRegionsError> {
+			let owner = psp34::PSP34Impl::owner_of(self, Id::U128(region_id))
+				.ok_or(XcRegionsError::MetadataNotFound)?;
+			ensure!(owner == self.env().caller(), XcRegionsError::NotOwner);
+			ensure!(!self.pending_regions.contains(region_id), XcRegionsError::RegionPending);
+			ensure!(!self.assignments.contains(region_id), XcRegionsError::RegionAssigned);
+			ensure!(!self.pooled_regions.contains(region_id), XcRegionsError::RegionPooled);
+
+			self.regions.get(region_id).ok_or(XcRegionsError::MetadataNotFound)
+		}
+
+		/// Burns the wrapped region of `owner`, and returns the metadata of the underlying region
+		/// it represents a part of.
+		fn _burn_derived(
+			&mut self,
+			owner: AccountId,
+			region_id: RawRegionId,
+		) -> Result<Region, XcRegionsError> {
+			let underlying =
+				self._underlying_region(region_id).ok_or(XcRegionsError::MetadataNotFound)?;
+
+			psp34::InternalImpl::_burn_from(self, owner, Id::U128(region_id))
+				.map_err(XcRegionsError::Psp34)?;
+			self.regions.remove(region_id);
+			if self.underlying_regions.contains(region_id) {
+				self.underlying_regions.remove(region_id);
+				self._remove_derived_piece(&underlying);
+			}
+
+			Ok(underlying)
+		}
+
+		/// Returns the metadata of the underlying region the wrapped region represents a part of.
+		///
+		/// Regions which weren't derived within the contract are their own underlying region.
+		fn _underlying_region(&self, region_id: RawRegionId) -> Option<Region> {
+			self.underlying_regions.get(region_id).or_else(|| self.regions.get(region_id))
+		}
+
+		/// Mints a wrapped region derived from the `underlying` region to `owner`, and returns the
+		/// version of its metadata.
+		fn _mint_derived(
+			&mut self,
+			owner: AccountId,
+			region_id: RawRegionId,
+			region: Region,
+			underlying: &Region,
+		) -> Result<Version, XcRegionsError> {
+			let version = self._store_metadata(region_id, &region, owner);
+			self.underlying_regions.insert(region_id, underlying);
+
+			let underlying_id = RawRegionId::from(underlying.id());
+			let pieces = self.derived_pieces.get(underlying_id).unwrap_or_default();
+			self.derived_pieces.insert(underlying_id, &pieces.saturating_add(1));
+
+			psp34::InternalImpl::_mint_to(self, owner, Id::U128(region_id))
+				.map_err(XcRegionsError::Psp34)?;
+
+			Ok(version)
+		}
+
+		/// Decrements the number of wrapped regions derived from the `underlying` region, and
+		/// returns the number of the remaining ones.
+		fn _remove_derived_piece(&mut self, underlying: &Region) -> u32 {
+			let underlying_id = RawRegionId::from(underlying.id());
+			let pieces =
+				self.derived_pieces.get(underlying_id).unwrap_or_default().saturating_sub(1);
+
+			if pieces == 0 {
+				self.derived_pieces.remove(underlying_id);
+			} else {
+				self.derived_pieces.insert(underlying_id, &pieces);
+			}
+
+			pieces
+		}
+	}
+
+	// Internal functions:
+	#[cfg(not(test))]
+	impl XcRegions {
+		fn _transfer(&self, region_id: RawRegionId, dest: AccountId) -> Result<(), XcRegionsError> {
+			let collection = self.regions_collection_id;
+			let call = match self.backend {
+				Backend::Uniques(indices) => RuntimeCall::Uniques(
+					indices,
+					UniquesCall::Transfer { collection, item: region_id, dest: dest.into() },
+				),
+				Backend::Nfts(indices) => RuntimeCall::Nfts(
+					indices,
+					NftsCall::Transfer { collection, item: region_id, dest: dest.into() },
+				),
+			};
+
+			self.env().call_runtime(&call).map_err(|_| XcRegionsError::RuntimeError)?;
+
+			Ok(())
+		}
+
+		/// Returns whether the region exists on this chain or not.
+		fn _uniques_exists(&self, region_id: RawRegionId) -> bool {
+			self._uniques_item(region_id).is_some()
+		}
+
+		/// Returns the details of an item within a collection.
+		///
+		/// In case of pallet-nfts the details are converted to their pallet-uniques counterpart,
+		/// with the item reported as frozen if its settings lock transfers. If the settings can't
+		/// be read the item is reported as frozen as well.
+		fn _uniques_item(&self, item_id: RawRegionId) -> Option<ItemDetails> {
+			let collection = self.regions_collection_id;
+			let extension = self.env().extension();
+
+			match self.backend {
+				Backend::Uniques(_) =>
+					UniquesExtension::item(&extension, collection, item_id).ok()?,
+				Backend::Nfts(_) => {
+					let item = NftsExtension::item(&extension, collection, item_id).ok()??;
+					let contract = self.env().account_id();
+					// pallet-nfts supports multiple approvals, so the contract is reported as the
+					// approved account if it is among them.
+					let approved = if item.is_approved(&contract, self.env().block_number()) {
+						Some(contract)
+					} else {
+						item.approvals.first().map(|(account, _)| *account)
+					};
+
+					let is_frozen = NftsExtension::item_settings(&extension, collection, item_id)
+						.ok()
+						.flatten()
+						.map_or(true, |settings| settings.is_locked());
+
+					Some(ItemDetails {
+						owner: item.owner,
+						approved,
+						is_frozen,
+						deposit: item.deposit.amount,
+					})
+				},
+			}
+		}
+
+		/// The owner of the specific item.
+		fn _uniques_owner(&self, region_id: RawRegionId) -> Option<AccountId> {
+			let collection = self.regions_collection_id;
+			let extension = self.env().extension();
+
+			match self.backend {
+				Backend::Uniques(_) =>
+					UniquesExtension::owner(&extension, collection, region_id).ok()?,
+				Backend::Nfts(_) => NftsExtension::owner(&extension, collection, region_id).ok()?,
+			}
+		}
+
+		/// Returns whether the collection of the regions is frozen.
+		///
+		/// In case of pallet-nfts the collection is frozen if its settings lock the transfers of
+		/// its items.
+		fn _uniques_collection_frozen(&self) -> bool {
+			match self.backend {
+				Backend::Uniques(_) => UniquesExtension::collection(
+					&self.env().extension(),
+					self.regions_collection_id,
+				)
+				.ok()
+				.flatten()
+				.map_or(false, |collection| collection.is_frozen),
+				Backend::Nfts(_) => NftsExtension::collection_settings(
+					&self.env().extension(),
+					self.regions_collection_id,
+				)
+				.ok()
+				.flatten()
+				.map_or(false, |settings| settings.is_locked()),
+			}
+		}
+
+		/// Cancels the approval of `delegate` to transfer the region.
+		fn _cancel_approval(
+			&self,
+			region_id: RawRegionId,
+			delegate: AccountId,
+		) -> Result<(), XcRegionsError> {
+			let collection = self.regions_collection_id;
+			let call = match self.backend {
+				Backend::Uniques(indices) => RuntimeCall::Uniques(
+					indices,
+					UniquesCall::CancelApproval {
+						collection,
+						item: region_id,
+						maybe_check_delegate: Some(delegate.into()),
+					},
+				),
+				Backend::Nfts(indices) => RuntimeCall::Nfts(
+					indices,
+					NftsCall::CancelApproval {
+						collection,
+						item: region_id,
+						delegate: delegate.into(),
+					},
+				),
+			};
+
+			self.env().call_runtime(&call).map_err(|_| XcRegionsError::RuntimeError)?;
+
+			Ok(())
+		}
+
+		/// The current timeslice, based on the latest known relay chain block number.
+		fn _current_timeslice(&self) -> Timeslice {
+			let latest_rc_block =
+				self.env().extension().relay_chain_block_number().unwrap_or_default();
+			block_to_timeslice(latest_rc_block, self.timeslice_period)
+		}
+
+		/// The state root of the Coretime chain at the specified block.
+		fn _coretime_state_root(&self, block: BlockNumber) -> Option<Hash> {
+			self.env().extension().coretime_state_root(block).ok()?
+		}
+
+		/// The latest Coretime chain block whose state root is known.
+		fn _latest_coretime_block(&self) -> BlockNumber {
+			self.env().extension().latest_coretime_block().unwrap_or_default()
+		}
+
+		/// Returns whether `signature` is a valid signature of `message` by `relayer`.
+		///
+		/// The signature is verified by the runtime through the chain extension, using the scheme
+		/// of the relayer's key.
+		fn _verify_attestation(
+			&self,
+			relayer: &RelayerKey,
+			signature: &Attestation,
+			message: &[u8],
+		) -> bool {
+			let extension = self.env().extension();
+			let message = message.to_vec();
+
+			match *relayer {
+				RelayerKey::Sr25519(public) => SignatureVerifierExtension::sr25519_verify(
+					&extension, *signature, message, public,
+				),
+				RelayerKey::Ed25519(public) => SignatureVerifierExtension::ed25519_verify(
+					&extension, *signature, message, public,
+				),
+			}
+			.unwrap_or(false)
+		}
+
+		/// Transfers `amount` relay chain tokens from `payer` to the contract, out of the amount
+		/// `payer` approved the contract to spend.
+		fn _collect_fee(
+			&self,
+			payer: AccountId,
+			amount: Balance,
+			fee_asset: &FeeAsset,
+		) -> Result<(), XcRegionsError> {
+			if amount == 0 {
+				return Ok(())
+			}
+
+			let call = RuntimeCall::Assets(
+				fee_asset.indices,
+				AssetsCall::TransferApproved {
+					id: fee_asset.asset_id,
+					owner: payer.into(),
+					destination: self.env().account_id().into(),
+					amount,
+				},
+			);
+
+			self.env().call_runtime(&call).map_err(|_| XcRegionsError::MissingFee)
+		}
+
+		/// Executes the `message` withdrawing the region from the contract.
+		fn _execute_region_xcm(
+			&self,
+			message: Xcm<()>,
+			indices: XcmCallIndices,
+			max_weight: (u64, u64),
+		) -> Result<(), XcRegionsError> {
+			let (ref_time, proof_size) = max_weight;
+			let call = RuntimeCall::Xcm(
+				indices,
+				XcmCall::Execute {
+					message: VersionedXcm::V3(message),
+					max_weight: Weight::from_parts(ref_time, proof_size),
+				},
+			);
+
+			self.env().call_runtime(&call).map_err(|_| XcRegionsError::RuntimeError)?;
+
+			Ok(())
+		}
+
+		/// Dispatches the `call` on the Coretime chain.
+		fn _transact_on_coretime(
+			&self,
+			call: RuntimeCall,
+			config: &TransactConfig,
+		) -> Result<(), XcRegionsError> {
+			let (ref_time, proof_size) = config.transact_weight;
+			let message = transact_on_coretime(
+				scale::Encode::encode(&call),
+				config.fee,
+				Weight::from_parts(ref_time, proof_size),
+			);
+			let dest = MultiLocation::new(1, X1(Parachain(config.coretime_para_id)));
+
+			let call = RuntimeCall::Xcm(
+				config.xcm_indices,
+				XcmCall::Send { dest: dest.into(), message: VersionedXcm::V3(message) },
+			);
+
+			self.env().call_runtime(&call).map_err(|_| XcRegionsError::RuntimeError)?;
+
+			Ok(())
+		}
+	}
+
+	// Implelementation of internal functions used only for integration tests.
+	#[cfg(test)]
+	impl XcRegions {
+		fn _transfer(
+			&mut self,
+			region_id: RawRegionId,
+			dest: AccountId,
+		) -> Result<(), XcRegionsError> {
+			// Just like in the pallet, transferring the item clears its approvals. Reminting the
+			// item does so, since it is minted without any approval.
+			self.burn((self.regions_collection_id, region_id)).unwrap();
+			self.mint((self.regions_collection_id, region_id), dest).unwrap();
+			Ok(())
+		}
+
+		/// Returns whether the region exists on this chain or not.
+		pub fn _uniques_exists(&self, region_id: RawRegionId) -> bool {
+			self._uniques_item(region_id).is_some()
+		}
+
+		/// Returns the details of an item within a collection.
+		pub fn _uniques_item(&self, item_id: RawRegionId) -> Option<ItemDetails> {
+			self.items.get((self.regions_collection_id, item_id))
+		}
+
+		/// The owner of the specific item.
+		pub fn _uniques_owner(&self, region_id: RawRegionId) -> Option<AccountId> {
+			self.items.get((self.regions_collection_id, region_id)).map(|a| a.owner)
+		}
+
+		/// The state root of the Coretime chain at the specified block.
+		pub fn _coretime_state_root(&self, block: BlockNumber) -> Option<Hash> {
+			self.state_roots.get(block)
+		}
+
+		/// The latest Coretime chain block whose state root is known.
+		pub fn _latest_coretime_block(&self) -> BlockNumber {
+			self.latest_coretime_block
+		}
+
+		/// Returns whether the collection of the regions is frozen.
+		pub fn _uniques_collection_frozen(&self) -> bool {
+			self.frozen_collections.contains(self.regions_collection_id)
+		}
+
+		/// Cancels the approval of `delegate` to transfer the region.
+		pub fn _cancel_approval(
+			&mut self,
+			region_id: RawRegionId,
+			delegate: AccountId,
+		) -> Result<(), XcRegionsError> {
+			let id = (self.regions_collection_id, region_id);
+			let mut item = self.items.get(id).ok_or(XcRegionsError::RuntimeError)?;
+			ensure!(item.approved == Some(delegate), XcRegionsError::RuntimeError);
+
+			item.approved = None;
+			self.items.insert(id, &item);
+			Ok(())
+		}
+
+		/// The current timeslice, based on the block number of this chain.
+		pub fn _current_timeslice(&self) -> Timeslice {
+			block_to_timeslice(self.env().block_number(), self.timeslice_period)
+		}
+
+		pub fn set_coretime_state_root(&mut self, block: BlockNumber, state_root: Hash) {
+			self.state_roots.insert(block, &state_root);
+			self.latest_coretime_block = self.latest_coretime_block.max(block);
+		}
+
+		/// Verifies the signature the same way the runtime does.
+		pub fn _verify_attestation(
+			&self,
+			relayer: &RelayerKey,
+			signature: &Attestation,
+			message: &[u8],
+		) -> bool {
+			match *relayer {
+				RelayerKey::Sr25519(public) => {
+					let Ok(public) = schnorrkel::PublicKey::from_bytes(&public) else {
+						return false
+					};
+					let Ok(signature) = schnorrkel::Signature::from_bytes(signature) else {
+						return false
+					};
+					public.verify_simple(b"substrate", message, &signature).is_ok()
+				},
+				RelayerKey::Ed25519(public) => {
+					use ed25519_dalek::Verifier;
+
+					let Ok(public) = ed25519_dalek::VerifyingKey::from_bytes(&public) else {
+						return false
+					};
+					public.verify(message, &ed25519_dalek::Signature::from_bytes(signature)).is_ok()
+				},
+			}
+		}
+
+		/// Transfers the fee out of the allowance set through `approve_fee`.
+		pub fn _collect_fee(
+			&mut self,
+			payer: AccountId,
+			amount: Balance,
+			_fee_asset: &FeeAsset,
+		) -> Result<(), XcRegionsError> {
+			if amount == 0 {
+				return Ok(())
+			}
+
+			let allowance = self.fee_allowances.get(payer).unwrap_or_default();
+			ensure!(allowance >= amount, XcRegionsError::MissingFee);
+			self.fee_allowances.insert(payer, &(allowance - amount));
+			self.collected_fees += amount;
+
+			Ok(())
+		}
+
+		/// Does nothing, since the caller records the XCM through `_record_region_xcm`.
+		pub fn _execute_region_xcm(
+			&mut self,
+			_message: Xcm<()>,
+			_indices: XcmCallIndices,
+			_max_weight: (u64, u64),
+		) -> Result<(), XcRegionsError> {
+			Ok(())
+		}
+
+		/// Records the XCM withdrawing the region instead of executing it, and burns the region
+		/// as its execution would.
+		pub fn _record_region_xcm(&mut self, region_id: RawRegionId, message: &Xcm<()>) {
+			self.burn((self.regions_collection_id, region_id)).unwrap();
+			self.executed_xcms
+				.insert(region_id, &scale::Encode::encode(&VersionedXcm::V3(message.clone())));
+		}
+
+		/// Records the XCM sent to the Coretime chain instead of sending it.
+		pub fn _transact_on_coretime(
+			&mut self,
+			call: RuntimeCall,
+			config: &TransactConfig,
+		) -> Result<(), XcRegionsError> {
+			let (ref_time, proof_size) = config.transact_weight;
+			let message = transact_on_coretime(
+				scale::Encode::encode(&call),
+				config.fee,
+				Weight::from_parts(ref_time, proof_size),
+			);
+
+			let mut sent_xcms = self.sent_xcms.get_or_default();
+			sent_xcms.push(scale::Encode::encode(&VersionedXcm::V3(message)));
+			self.sent_xcms.set(&sent_xcms);
+			Ok(())
+		}
+
+		/// Mints the item to `owner`. Just like in the pallet, the item has no approved account.
+		pub fn mint(
+			&mut self,
+			id: (CollectionId, RawRegionId),
+			owner: AccountId,
+		) -> Result<(), &'static str> {
+			ensure!(self.items.get((id.0, id.1)).is_none(), "Item already exists");
+			self.items.insert(
+				(id.0, id.1),
+				&ItemDetails {
+					owner,
 					approved: None,
 					is_frozen: false,
 					deposit: Default::default(),
@@ -321,6 +2674,25 @@ pub mod xc_regions {
 			Ok(())
 		}
 
+		/// Approves `delegate` to transfer the item, replacing any previous approval.
+		pub fn approve_transfer(
+			&mut self,
+			id: (CollectionId, RawRegionId),
+			delegate: AccountId,
+		) -> Result<(), &'static str> {
+			let Some(mut item) = self.items.get((id.0, id.1)) else { return Err("Item not found") };
+			item.approved = Some(delegate);
+			self.items.insert((id.0, id.1), &item);
+
+			Ok(())
+		}
+
+		/// Approves the contract to spend `amount` relay chain tokens of `owner` for paying fees,
+		/// replacing any previous approval.
+		pub fn approve_fee(&mut self, owner: AccountId, amount: Balance) {
+			self.fee_allowances.insert(owner, &amount);
+		}
+
 		pub fn burn(&mut self, id: (CollectionId, RawRegionId)) -> Result<(), &'static str> {
 			let Some(owner) = self.items.get((id.0, id.1)).map(|a| a.owner) else {
 				return Err("Item not found")
@@ -344,10 +2716,7 @@ pub mod xc_regions {
 	#[cfg(all(test, feature = "e2e-tests"))]
 	pub mod tests {
 		use super::*;
-		use crate::{
-			traits::regionmetadata_external::RegionMetadata, types::VersionedRegion,
-			REGIONS_COLLECTION_ID,
-		};
+		use crate::{traits::regionmetadata_external::RegionMetadata, types::VersionedRegion};
 		use environment::ExtendedEnvironment;
 		use ink_e2e::{subxt::dynamic::Value, MessageBuilder};
 		use openbrush::contracts::psp34::psp34_external::PSP34;
@@ -355,11 +2724,13 @@ pub mod xc_regions {
 
 		type E2EResult<T> = Result<T, Box<dyn std::error::Error>>;
 
+		const REGIONS_COLLECTION_ID: CollectionId = 42;
+
 		#[ink_e2e::test(environment = ExtendedEnvironment)]
 		async fn init_non_existing_region_fails(
 			mut client: ink_e2e::Client<C, E>,
 		) -> E2EResult<()> {
-			let constructor = XcRegionsRef::new();
+			let constructor = XcRegionsRef::new(REGIONS_COLLECTION_ID, Default::default());
 			let contract_acc_id = client
 				.instantiate("xc-regions", &ink_e2e::alice(), constructor, 0, None)
 				.await
@@ -381,7 +2752,7 @@ pub mod xc_regions {
 
 		#[ink_e2e::test(environment = ExtendedEnvironment)]
 		async fn init_works(mut client: E2EBackend) -> E2EResult<()> {
-			let constructor = XcRegionsRef::new();
+			let constructor = XcRegionsRef::new(REGIONS_COLLECTION_ID, Default::default());
 			let contract_acc_id = client
 				.instantiate("xc-regions", &ink_e2e::alice(), constructor, 0, None)
 				.await
@@ -463,7 +2834,7 @@ pub mod xc_regions {
 
 		#[ink_e2e::test(environment = ExtendedEnvironment)]
 		async fn remove_works(mut client: E2EBackend) -> E2EResult<()> {
-			let constructor = XcRegionsRef::new();
+			let constructor = XcRegionsRef::new(REGIONS_COLLECTION_ID, Default::default());
 			let contract_acc_id = client
 				.instantiate("xc-regions", &ink_e2e::alice(), constructor, 0, None)
 				.await
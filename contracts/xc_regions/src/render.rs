@@ -0,0 +1,122 @@
+// This file is part of RegionX.
+//
+// RegionX is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// RegionX is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with RegionX.  If not, see <https://www.gnu.org/licenses/>.
+
+//! On-chain rendering of wrapped regions as SVG images.
+
+use crate::types::{RegionStatus, VersionedRegion};
+use ink::prelude::{format, string::String};
+use primitives::coretime::{Timeslice, CORE_MASK_BIT_LEN};
+
+/// The horizontal padding of the image.
+const PADDING: u32 = 40;
+/// The width of a single bit of the core mask.
+const BIT_WIDTH: u32 = 5;
+/// The width of the core mask bar and of the timeline.
+const BAR_WIDTH: u32 = BIT_WIDTH * CORE_MASK_BIT_LEN as u32;
+/// The width of the image.
+const WIDTH: u32 = BAR_WIDTH + 2 * PADDING;
+/// The height of the image.
+const HEIGHT: u32 = 200;
+
+/// The prefix of the data URIs returned by `token_uri`.
+pub const SVG_DATA_URI_PREFIX: &str = "data:image/svg+xml;utf8,";
+
+/// Returns the SVG image of the region as a data URI.
+pub fn token_uri(versioned: &VersionedRegion, current_timeslice: Timeslice) -> String {
+	let svg = render_svg(versioned, current_timeslice);
+
+	let mut uri = String::from(SVG_DATA_URI_PREFIX);
+	for c in svg.chars() {
+		match c {
+			'%' => uri.push_str("%25"),
+			'#' => uri.push_str("%23"),
+			'<' => uri.push_str("%3C"),
+			'>' => uri.push_str("%3E"),
+			'"' => uri.push_str("%22"),
+			' ' => uri.push_str("%20"),
+			c => uri.push(c),
+		}
+	}
+
+	uri
+}
+
+/// Renders the region as an SVG image.
+///
+/// The image shows the core of the region, its core mask as a bar in which each set bit is
+/// filled, and a timeline from the beginning to the end of the region with a marker at the
+/// current timeslice. The rendering only depends on the arguments, so the same region always
+/// results in the same image at the same timeslice.
+pub fn render_svg(versioned: &VersionedRegion, current_timeslice: Timeslice) -> String {
+	let region = &versioned.region;
+	let (status, color) = match RegionStatus::of(region, current_timeslice) {
+		RegionStatus::Inactive => ("Inactive", "#f2a900"),
+		RegionStatus::Active => ("Active", "#2ecc71"),
+		RegionStatus::Expired => ("Expired", "#7f8c8d"),
+	};
+
+	let mut svg = format!(
+		"<svg xmlns='http://www.w3.org/2000/svg' width='{WIDTH}' height='{HEIGHT}' \
+		 viewBox='0 0 {WIDTH} {HEIGHT}'>\
+		 <rect width='{WIDTH}' height='{HEIGHT}' fill='#101820'/>\
+		 <text x='{PADDING}' y='35' fill='#ffffff' font-family='monospace' font-size='18'>\
+		 Core {} - v{}</text>\
+		 <text x='{}' y='35' fill='{color}' font-family='monospace' font-size='18' \
+		 text-anchor='end'>{status}</text>\
+		 <rect x='{PADDING}' y='60' width='{BAR_WIDTH}' height='30' fill='#2c3e50'/>",
+		region.core,
+		versioned.version,
+		WIDTH - PADDING,
+	);
+
+	// Consecutive set bits are drawn as a single rectangle.
+	let mut run_start = None;
+	for bit in 0..=CORE_MASK_BIT_LEN as u32 {
+		match (region.mask.is_set(bit), run_start) {
+			(true, None) => run_start = Some(bit),
+			(false, Some(start)) => {
+				svg.push_str(&format!(
+					"<rect x='{}' y='60' width='{}' height='30' fill='{color}'/>",
+					PADDING + start * BIT_WIDTH,
+					(bit - start) * BIT_WIDTH,
+				));
+				run_start = None;
+			},
+			_ => {},
+		}
+	}
+
+	// The marker of the current timeslice is clamped to the timeline.
+	let length = region.end.saturating_sub(region.begin).max(1);
+	let elapsed =
+		current_timeslice.clamp(region.begin, region.begin.saturating_add(length)) - region.begin;
+	let marker = PADDING + (u64::from(elapsed) * u64::from(BAR_WIDTH) / u64::from(length)) as u32;
+
+	svg.push_str(&format!(
+		"<line x1='{PADDING}' y1='140' x2='{}' y2='140' stroke='#ffffff' stroke-width='2'/>\
+		 <circle cx='{marker}' cy='140' r='6' fill='{color}'/>\
+		 <text x='{PADDING}' y='170' fill='#ffffff' font-family='monospace' \
+		 font-size='14'>{}</text>\
+		 <text x='{}' y='170' fill='#ffffff' font-family='monospace' font-size='14' \
+		 text-anchor='end'>{}</text>\
+		 </svg>",
+		WIDTH - PADDING,
+		region.begin,
+		WIDTH - PADDING,
+		region.end,
+	));
+
+	svg
+}
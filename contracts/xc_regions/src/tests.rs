@@ -14,29 +14,67 @@
 // along with RegionX.  If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
-	traits::RegionMetadata,
-	types::{VersionedRegion, XcRegionsError},
-	xc_regions::{RegionInitialized, RegionRemoved, XcRegions},
-	REGIONS_COLLECTION_ID,
+	render::{render_svg, SVG_DATA_URI_PREFIX},
+	traits::{RegionMetadata, RegionState},
+	types::{
+		Assignment, Attestation, AttestationNonce, Backend, ChallengeEvidence, FeeAsset,
+		MetadataHistoryPage, MetadataRecord, OptimisticConfig, PendingRegion, PooledRegion,
+		ReapConfig, RegionFilter, RegionStatus, RegionsPage, RelayerKey, TeleportConfig,
+		TransactConfig, VersionedRegion, XcRegionsError, MAX_BATCH_SIZE, MAX_PAGE_SIZE,
+		MAX_STATE_ROOT_AGE,
+	},
+	xc_regions::{
+		AssignmentRequested, RegionAssigned, RegionAttested, RegionChallenged, RegionFinalized,
+		RegionInitialized, RegionInterlaced, RegionPartitioned, RegionPooled, RegionReaped,
+		RegionRemoved, RegionTeleported, RegionTransferred, RegionsMerged, RetrievalRequested,
+		RevenueClaimRequested, RevenueClaimed, XcRegions,
+	},
 };
+use ed25519_dalek::{Signer, SigningKey};
 use ink::env::{
-	test::{default_accounts, set_caller, DefaultAccounts},
+	test::{
+		advance_block, default_accounts, get_account_balance, set_account_balance, set_caller,
+		set_value_transferred, DefaultAccounts,
+	},
 	DefaultEnvironment,
 };
-use openbrush::contracts::psp34::{Id, PSP34};
+use openbrush::{
+	contracts::{
+		ownable::{Ownable, OwnableError},
+		psp34::{extensions::metadata::PSP34Metadata, Id, PSP34Error, PSP34},
+	},
+	traits::{AccountId, BlockNumber, String},
+};
 use primitives::{
 	assert_ok,
-	coretime::{RawRegionId, Region},
-	uniques::{CollectionId, ItemDetails},
-	Version,
+	broker::BrokerCall,
+	coretime::{
+		CoreMask, Finality, ParaId, RawRegionId, Region, RegionError, RegionId, RegionRecord,
+		Timeslice,
+	},
+	nfts::NftsCallIndices,
+	pallet_xcm::{
+		reserve_transfer_region, reserve_withdraw_region, reserve_withdraw_region_to,
+		sibling_account, transact_on_coretime, Weight, XcmCall,
+	},
+	proof::{prove_region, ProofError},
+	uniques::{CollectionId, ItemDetails, UniquesCallIndices},
+	RuntimeCall, Version,
+};
+use schnorrkel::{ExpansionMode, Keypair, MiniSecretKey};
+use xcm::{
+	v3::{Junction::Parachain, Junctions::*, MultiLocation},
+	VersionedMultiLocation, VersionedXcm,
 };
 
 type Event = <XcRegions as ::ink::reflect::ContractEventBase>::Type;
 
+const REGIONS_COLLECTION_ID: CollectionId = 42;
+
 #[ink::test]
 fn mock_environment_helper_functions_work() {
 	let DefaultAccounts::<DefaultEnvironment> { charlie, .. } = get_default_accounts();
-	let mut xc_regions = XcRegions::new();
+	let mut xc_regions = XcRegions::new(REGIONS_COLLECTION_ID, Default::default());
 
 	let region_id_0 = region_id(0);
 
@@ -78,10 +116,50 @@ fn mock_environment_helper_functions_work() {
 	assert!(xc_regions.burn(region_id_1).is_err());
 }
 
+#[ink::test]
+fn regions_collection_id_works() {
+	let DefaultAccounts::<DefaultEnvironment> { charlie, .. } = get_default_accounts();
+	let mut xc_regions = XcRegions::new(7, Default::default());
+	set_caller::<DefaultEnvironment>(charlie);
+
+	let contract = ink::env::account_id::<DefaultEnvironment>();
+
+	assert_eq!(xc_regions.regions_collection_id(), 7);
+	assert_eq!(xc_regions.collection_id(), Id::U32(7));
+
+	// The underlying region is looked up in the configured collection:
+	assert_ok!(xc_regions.mint((REGIONS_COLLECTION_ID, 0), charlie));
+	assert_eq!(
+		xc_regions.init(Id::U128(0), Region::default()),
+		Err(XcRegionsError::CannotInitialize)
+	);
+
+	assert_ok!(xc_regions.mint((7, 0), charlie));
+	approve_contract(&mut xc_regions, (7, 0));
+	assert_ok!(xc_regions.init(Id::U128(0), Region::default()));
+	assert_eq!(xc_regions.items.get((7, 0)).map(|item| item.owner), Some(contract));
+}
+
+#[ink::test]
+fn backend_works() {
+	let xc_regions = XcRegions::new(REGIONS_COLLECTION_ID, Default::default());
+	assert_eq!(xc_regions.backend(), Backend::Uniques(Default::default()));
+
+	let indices =
+		UniquesCallIndices { pallet: 12, transfer: 1, approve_transfer: 2, cancel_approval: 3 };
+	let xc_regions = XcRegions::new(REGIONS_COLLECTION_ID, Backend::Uniques(indices));
+	assert_eq!(xc_regions.backend(), Backend::Uniques(indices));
+
+	let indices =
+		NftsCallIndices { pallet: 52, transfer: 6, approve_transfer: 15, cancel_approval: 16 };
+	let xc_regions = XcRegions::new(REGIONS_COLLECTION_ID, Backend::Nfts(indices));
+	assert_eq!(xc_regions.backend(), Backend::Nfts(indices));
+}
+
 #[ink::test]
 fn init_works() {
 	let DefaultAccounts::<DefaultEnvironment> { charlie, bob, .. } = get_default_accounts();
-	let mut xc_regions = XcRegions::new();
+	let mut xc_regions = XcRegions::new(REGIONS_COLLECTION_ID, Default::default());
 	let contract = ink::env::account_id::<ink::env::DefaultEnvironment>();
 
 	// 1. Cannot initialize a region that doesn't exist:
@@ -93,6 +171,7 @@ fn init_works() {
 
 	// 2. Cannot initialize a region that is not owned by the caller
 	assert_ok!(xc_regions.mint(region_id(0), charlie));
+	approve_contract(&mut xc_regions, region_id(0));
 
 	set_caller::<DefaultEnvironment>(bob);
 
@@ -114,8 +193,9 @@ fn init_works() {
 	assert_ok!(xc_regions.init(Id::U128(0), Region::default()));
 
 
-	// The region gets transferred to the contract:
+	// The region gets transferred to the contract, which clears the approval:
 	assert_eq!(xc_regions._uniques_owner(0), Some(contract));
+	assert_eq!(xc_regions._uniques_item(0).unwrap().approved, None);
 
 	// Charlie receives a wrapped region:
 	assert_eq!(xc_regions.owner_of(Id::U128(0)), Some(charlie));
@@ -135,10 +215,547 @@ fn init_works() {
 	);
 }
 
+#[ink::test]
+fn init_with_proof_works() {
+	let DefaultAccounts::<DefaultEnvironment> { charlie, bob, .. } = get_default_accounts();
+	let mut xc_regions = XcRegions::new(REGIONS_COLLECTION_ID, Default::default());
+	set_caller::<DefaultEnvironment>(charlie);
+
+	let contract = ink::env::account_id::<DefaultEnvironment>();
+
+	let region_id = RegionId { begin: 2, core: 1, mask: CoreMask::complete() };
+	let raw_region_id = RawRegionId::from(region_id);
+	let record = RegionRecord { end: 10, owner: bob, paid: Some(100) };
+	let other_id = RegionId { begin: 2, core: 2, mask: CoreMask::complete() };
+	let entries = vec![
+		(region_id, scale::Encode::encode(&record)),
+		(other_id, scale::Encode::encode(&RegionRecord { end: 12, owner: bob, paid: None })),
+	];
+	let (state_root, proof) = prove_region(&entries, &region_id);
+
+	// 1. Cannot initialize a region that doesn't exist on this chain:
+	assert_eq!(
+		xc_regions.init_with_proof(Id::U128(raw_region_id), 1, proof.clone()),
+		Err(XcRegionsError::CannotInitialize)
+	);
+
+	assert_ok!(xc_regions.mint((REGIONS_COLLECTION_ID, raw_region_id), charlie));
+	approve_contract(&mut xc_regions, (REGIONS_COLLECTION_ID, raw_region_id));
+
+	// 2. The state root must be known to the contract:
+	assert_eq!(
+		xc_regions.init_with_proof(Id::U128(raw_region_id), 1, proof.clone()),
+		Err(XcRegionsError::StateRootNotFound)
+	);
+
+	xc_regions.set_coretime_state_root(1, state_root);
+
+	// 3. The proof must cover the region record:
+	let (_, other_proof) = prove_region(&entries, &other_id);
+	assert_eq!(
+		xc_regions.init_with_proof(Id::U128(raw_region_id), 1, other_proof),
+		Err(XcRegionsError::InvalidProof(ProofError::IncompleteProof))
+	);
+
+	// 4. The state root can't be older than the latest known one by more than
+	// `max_state_root_age` blocks:
+	assert_eq!(xc_regions.max_state_root_age(), MAX_STATE_ROOT_AGE);
+	let latest_block = MAX_STATE_ROOT_AGE + 2;
+	xc_regions.set_coretime_state_root(latest_block, state_root);
+	assert_eq!(
+		xc_regions.init_with_proof(Id::U128(raw_region_id), 1, proof.clone()),
+		Err(XcRegionsError::StateRootTooOld)
+	);
+
+	// 5. Initialization works with a valid proof. The metadata is taken from the proven record:
+	assert_ok!(xc_regions.init_with_proof(Id::U128(raw_region_id), latest_block, proof.clone()));
+
+	let region = Region { begin: 2, end: 10, core: 1, mask: CoreMask::complete() };
+	assert_eq!(xc_regions._uniques_owner(raw_region_id), Some(contract));
+	assert_eq!(xc_regions.owner_of(Id::U128(raw_region_id)), Some(charlie));
+	assert_eq!(xc_regions.regions.get(raw_region_id), Some(region.clone()));
+	assert_eq!(xc_regions.metadata_versions.get(raw_region_id), Some(0));
+
+	let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+	assert_init_event(&emitted_events.last().unwrap(), raw_region_id, region, 0);
+
+	// 6. Calling init for an already initialized region will fail.
+	assert_eq!(
+		xc_regions.init_with_proof(Id::U128(raw_region_id), latest_block, proof),
+		Err(XcRegionsError::CannotInitialize)
+	);
+}
+
+#[ink::test]
+fn set_optimistic_config_works() {
+	let DefaultAccounts::<DefaultEnvironment> { alice, bob, .. } = get_default_accounts();
+	set_caller::<DefaultEnvironment>(alice);
+	let mut xc_regions = XcRegions::new(REGIONS_COLLECTION_ID, Default::default());
+
+	// The deployer becomes the owner of the contract:
+	assert_eq!(xc_regions.owner(), Some(alice));
+	assert_eq!(xc_regions.optimistic_config(), None);
+
+	let config = OptimisticConfig { bond: 100, challenge_period: 10, arbiter: Some(bob) };
+
+	// Only the owner can update the configuration:
+	set_caller::<DefaultEnvironment>(bob);
+	assert_eq!(
+		xc_regions.set_optimistic_config(Some(config)),
+		Err(XcRegionsError::Ownable(OwnableError::CallerIsNotOwner))
+	);
+
+	set_caller::<DefaultEnvironment>(alice);
+	assert_ok!(xc_regions.set_optimistic_config(Some(config)));
+	assert_eq!(xc_regions.optimistic_config(), Some(config));
+
+	assert_ok!(xc_regions.set_optimistic_config(None));
+	assert_eq!(xc_regions.optimistic_config(), None);
+}
+
+#[ink::test]
+fn init_optimistic_works() {
+	let DefaultAccounts::<DefaultEnvironment> { alice, charlie, .. } = get_default_accounts();
+	set_caller::<DefaultEnvironment>(alice);
+	let mut xc_regions = XcRegions::new(REGIONS_COLLECTION_ID, Default::default());
+	let contract = ink::env::account_id::<DefaultEnvironment>();
+
+	set_caller::<DefaultEnvironment>(charlie);
+	assert_ok!(xc_regions.mint(region_id(0), charlie));
+	approve_contract(&mut xc_regions, region_id(0));
+
+	// 1. Optimistic initialization is disabled by default:
+	assert_eq!(
+		xc_regions.init_optimistic(Id::U128(0), Region::default()),
+		Err(XcRegionsError::OptimisticInitDisabled)
+	);
+
+	set_caller::<DefaultEnvironment>(alice);
+	let config = OptimisticConfig { bond: 100, challenge_period: 10, arbiter: None };
+	assert_ok!(xc_regions.set_optimistic_config(Some(config)));
+
+	// 2. The bond must be sent along:
+	set_caller::<DefaultEnvironment>(charlie);
+	set_value_transferred::<DefaultEnvironment>(50);
+	assert_eq!(
+		xc_regions.init_optimistic(Id::U128(0), Region::default()),
+		Err(XcRegionsError::MissingBond)
+	);
+
+	// 3. Initialization works with the bond. The region is pending until finalized:
+	set_value_transferred::<DefaultEnvironment>(100);
+	assert_ok!(xc_regions.init_optimistic(Id::U128(0), Region::default()));
+
+	assert_eq!(xc_regions._uniques_owner(0), Some(contract));
+	assert_eq!(xc_regions.owner_of(Id::U128(0)), Some(charlie));
+	assert_eq!(
+		xc_regions.get_metadata(Id::U128(0)),
+		Ok(VersionedRegion { version: 0, region: Region::default() })
+	);
+	assert!(xc_regions.is_pending(Id::U128(0)));
+	assert_eq!(
+		xc_regions.pending_region(Id::U128(0)),
+		Ok(PendingRegion { initializer: charlie, bond: 100, challenge_deadline: 10 })
+	);
+
+	// Pending regions can't be transferred or removed:
+	assert_eq!(
+		xc_regions.transfer(alice, Id::U128(0), vec![]),
+		Err(PSP34Error::Custom(String::from("RegionPending")))
+	);
+	assert_eq!(xc_regions.remove(Id::U128(0)), Err(XcRegionsError::RegionPending));
+
+	// 4. The region can only be finalized once the challenge period is over:
+	assert_eq!(xc_regions.finalize(Id::U128(0)), Err(XcRegionsError::ChallengePeriodNotOver));
+
+	(0..11).for_each(|_| advance_block::<DefaultEnvironment>());
+	set_account_balance::<DefaultEnvironment>(contract, 100);
+	let charlie_balance = get_account_balance::<DefaultEnvironment>(charlie).unwrap();
+
+	assert_ok!(xc_regions.finalize(Id::U128(0)));
+	assert!(!xc_regions.is_pending(Id::U128(0)));
+	assert_eq!(xc_regions.finalize(Id::U128(0)), Err(XcRegionsError::RegionNotPending));
+
+	// The bond is returned to the initializer:
+	assert_eq!(get_account_balance::<DefaultEnvironment>(charlie), Ok(charlie_balance + 100));
+
+	let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+	assert_finalized_event(&emitted_events.last().unwrap(), 0);
+
+	// Finalized regions can be transferred again:
+	assert_ok!(xc_regions.transfer(alice, Id::U128(0), vec![]));
+}
+
+#[ink::test]
+fn challenge_works() {
+	let DefaultAccounts::<DefaultEnvironment> { alice, bob, charlie, django, .. } =
+		get_default_accounts();
+	set_caller::<DefaultEnvironment>(alice);
+	let mut xc_regions = XcRegions::new(REGIONS_COLLECTION_ID, Default::default());
+	let contract = ink::env::account_id::<DefaultEnvironment>();
+
+	let config = OptimisticConfig { bond: 100, challenge_period: 10, arbiter: Some(bob) };
+	assert_ok!(xc_regions.set_optimistic_config(Some(config)));
+
+	// Charlie initializes a region with an incorrect end:
+	let region_id = RegionId { begin: 2, core: 1, mask: CoreMask::complete() };
+	let raw_region_id = RawRegionId::from(region_id);
+	let region = Region { begin: 2, end: 20, core: 1, mask: CoreMask::complete() };
+
+	set_caller::<DefaultEnvironment>(charlie);
+	set_value_transferred::<DefaultEnvironment>(100);
+	assert_ok!(xc_regions.mint((REGIONS_COLLECTION_ID, raw_region_id), charlie));
+	approve_contract(&mut xc_regions, (REGIONS_COLLECTION_ID, raw_region_id));
+	assert_ok!(xc_regions.init_optimistic(Id::U128(raw_region_id), region.clone()));
+
+	// 1. Only the arbiter can give a verdict:
+	set_caller::<DefaultEnvironment>(django);
+	assert_eq!(
+		xc_regions.challenge(Id::U128(raw_region_id), ChallengeEvidence::Verdict { end: 10 }),
+		Err(XcRegionsError::NotArbiter)
+	);
+
+	// 2. The challenge fails if the evidence shows that the metadata is correct:
+	set_caller::<DefaultEnvironment>(bob);
+	assert_eq!(
+		xc_regions.challenge(Id::U128(raw_region_id), ChallengeEvidence::Verdict { end: 20 }),
+		Err(XcRegionsError::MetadataNotFraudulent)
+	);
+
+	// 3. Anyone can challenge with a storage proof:
+	let record = RegionRecord { end: 10, owner: bob, paid: None };
+	let (state_root, proof) =
+		prove_region(&[(region_id, scale::Encode::encode(&record))], &region_id);
+	xc_regions.set_coretime_state_root(1, state_root);
+
+	set_account_balance::<DefaultEnvironment>(contract, 100);
+	let django_balance = get_account_balance::<DefaultEnvironment>(django).unwrap();
+
+	set_caller::<DefaultEnvironment>(django);
+	assert_ok!(xc_regions.challenge(
+		Id::U128(raw_region_id),
+		ChallengeEvidence::Proof { state_root_block: 1, proof: proof.clone() }
+	));
+
+	// The metadata gets corrected and the challenger receives the bond:
+	let corrected = Region { end: 10, ..region };
+	assert_eq!(
+		xc_regions.get_metadata(Id::U128(raw_region_id)),
+		Ok(VersionedRegion { version: 1, region: corrected.clone() })
+	);
+	assert!(!xc_regions.is_pending(Id::U128(raw_region_id)));
+	assert_eq!(get_account_balance::<DefaultEnvironment>(django), Ok(django_balance + 100));
+
+	let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+	assert_challenged_event(&emitted_events.last().unwrap(), raw_region_id, django, corrected, 1);
+
+	// 4. The region can't be challenged once it is no longer pending:
+	assert_eq!(
+		xc_regions.challenge(
+			Id::U128(raw_region_id),
+			ChallengeEvidence::Proof { state_root_block: 1, proof }
+		),
+		Err(XcRegionsError::RegionNotPending)
+	);
+
+	// 5. The region can't be challenged after the challenge period:
+	set_caller::<DefaultEnvironment>(charlie);
+	assert_ok!(xc_regions.mint((REGIONS_COLLECTION_ID, 0), charlie));
+	approve_contract(&mut xc_regions, (REGIONS_COLLECTION_ID, 0));
+	assert_ok!(xc_regions.init_optimistic(Id::U128(0), Region::default()));
+
+	(0..11).for_each(|_| advance_block::<DefaultEnvironment>());
+
+	set_caller::<DefaultEnvironment>(bob);
+	assert_eq!(
+		xc_regions.challenge(Id::U128(0), ChallengeEvidence::Verdict { end: 10 }),
+		Err(XcRegionsError::ChallengePeriodOver)
+	);
+}
+
+#[ink::test]
+fn relayer_management_works() {
+	let DefaultAccounts::<DefaultEnvironment> { alice, bob, .. } = get_default_accounts();
+	set_caller::<DefaultEnvironment>(alice);
+	let mut xc_regions = XcRegions::new(REGIONS_COLLECTION_ID, Default::default());
+
+	let relayer_1 = relayer_key(&relayer_keypair(1));
+	let relayer_2 = relayer_key(&relayer_keypair(2));
+
+	assert!(xc_regions.relayers().is_empty());
+	assert_eq!(xc_regions.attestation_threshold(), 0);
+
+	// Only the owner can manage the relayer set:
+	set_caller::<DefaultEnvironment>(bob);
+	assert_eq!(
+		xc_regions.add_relayer(relayer_1),
+		Err(XcRegionsError::Ownable(OwnableError::CallerIsNotOwner))
+	);
+
+	set_caller::<DefaultEnvironment>(alice);
+	assert_ok!(xc_regions.add_relayer(relayer_1));
+	assert_eq!(xc_regions.add_relayer(relayer_1), Err(XcRegionsError::RelayerAlreadyRegistered));
+	assert_ok!(xc_regions.add_relayer(relayer_2));
+	assert_eq!(xc_regions.relayers(), vec![relayer_1, relayer_2]);
+
+	// The threshold can't exceed the number of relayers:
+	assert_eq!(xc_regions.set_attestation_threshold(3), Err(XcRegionsError::InvalidThreshold));
+	assert_ok!(xc_regions.set_attestation_threshold(2));
+	assert_eq!(xc_regions.attestation_threshold(), 2);
+
+	assert_eq!(xc_regions.remove_relayer(relayer_1), Err(XcRegionsError::InvalidThreshold));
+	assert_ok!(xc_regions.set_attestation_threshold(1));
+	assert_ok!(xc_regions.remove_relayer(relayer_1));
+	assert_eq!(xc_regions.remove_relayer(relayer_1), Err(XcRegionsError::RelayerNotRegistered));
+	assert_eq!(xc_regions.relayers(), vec![relayer_2]);
+}
+
+#[ink::test]
+fn init_with_attestations_works() {
+	let DefaultAccounts::<DefaultEnvironment> { alice, charlie, .. } = get_default_accounts();
+	set_caller::<DefaultEnvironment>(alice);
+	let mut xc_regions = XcRegions::new(REGIONS_COLLECTION_ID, Default::default());
+	let contract = ink::env::account_id::<DefaultEnvironment>();
+
+	let region = Region::default();
+	let (relayer_1, relayer_2, relayer_3) =
+		(relayer_keypair(1), relayer_keypair(2), relayer_keypair(3));
+	let valid_until = 10;
+
+	set_caller::<DefaultEnvironment>(charlie);
+	assert_ok!(xc_regions.mint(region_id(0), charlie));
+	approve_contract(&mut xc_regions, region_id(0));
+
+	// 1. Initialization through attestations is disabled by default:
+	assert_eq!(
+		xc_regions.init_with_attestations(
+			Id::U128(0),
+			region.clone(),
+			0,
+			valid_until,
+			vec![attest(&relayer_1, 0, &region, 0, valid_until)]
+		),
+		Err(XcRegionsError::AttestationDisabled)
+	);
+
+	// Register two out of the three relayers, requiring both of them to attest:
+	set_caller::<DefaultEnvironment>(alice);
+	assert_ok!(xc_regions.add_relayer(relayer_key(&relayer_1)));
+	assert_ok!(xc_regions.add_relayer(relayer_key(&relayer_2)));
+	assert_ok!(xc_regions.set_attestation_threshold(2));
+
+	set_caller::<DefaultEnvironment>(charlie);
+
+	// 2. The attestations must include the current nonce:
+	assert_eq!(
+		xc_regions.init_with_attestations(
+			Id::U128(0),
+			region.clone(),
+			1,
+			valid_until,
+			vec![
+				attest(&relayer_1, 0, &region, 1, valid_until),
+				attest(&relayer_2, 0, &region, 1, valid_until)
+			]
+		),
+		Err(XcRegionsError::InvalidNonce)
+	);
+
+	// 3. The number of attestations must reach the threshold:
+	assert_eq!(
+		xc_regions.init_with_attestations(
+			Id::U128(0),
+			region.clone(),
+			0,
+			valid_until,
+			vec![attest(&relayer_1, 0, &region, 0, valid_until)]
+		),
+		Err(XcRegionsError::NotEnoughAttestations)
+	);
+
+	// 4. A relayer can't attest twice:
+	assert_eq!(
+		xc_regions.init_with_attestations(
+			Id::U128(0),
+			region.clone(),
+			0,
+			valid_until,
+			vec![
+				attest(&relayer_1, 0, &region, 0, valid_until),
+				attest(&relayer_1, 0, &region, 0, valid_until)
+			]
+		),
+		Err(XcRegionsError::InvalidAttestation)
+	);
+
+	// 5. Only the attestations of registered relayers are accepted:
+	assert_eq!(
+		xc_regions.init_with_attestations(
+			Id::U128(0),
+			region.clone(),
+			0,
+			valid_until,
+			vec![
+				attest(&relayer_1, 0, &region, 0, valid_until),
+				attest(&relayer_3, 0, &region, 0, valid_until)
+			]
+		),
+		Err(XcRegionsError::InvalidAttestation)
+	);
+
+	// 6. The attestations must be signed over the provided metadata:
+	let other_region = Region { end: 10, ..region.clone() };
+	assert_eq!(
+		xc_regions.init_with_attestations(
+			Id::U128(0),
+			region.clone(),
+			0,
+			valid_until,
+			vec![
+				attest(&relayer_1, 0, &region, 0, valid_until),
+				attest(&relayer_2, 0, &other_region, 0, valid_until)
+			]
+		),
+		Err(XcRegionsError::InvalidAttestation)
+	);
+
+	// 7. A relayer can't vouch for the signature of another relayer:
+	let (_, signature) = attest(&relayer_1, 0, &region, 0, valid_until);
+	assert_eq!(
+		xc_regions.init_with_attestations(
+			Id::U128(0),
+			region.clone(),
+			0,
+			valid_until,
+			vec![
+				attest(&relayer_1, 0, &region, 0, valid_until),
+				(relayer_key(&relayer_2), signature)
+			]
+		),
+		Err(XcRegionsError::InvalidAttestation)
+	);
+
+	// 8. Initialization works with enough valid attestations:
+	let attestations = vec![
+		attest(&relayer_2, 0, &region, 0, valid_until),
+		attest(&relayer_1, 0, &region, 0, valid_until),
+	];
+	assert_ok!(xc_regions.init_with_attestations(
+		Id::U128(0),
+		region.clone(),
+		0,
+		valid_until,
+		attestations.clone()
+	));
+
+	assert_eq!(xc_regions._uniques_owner(0), Some(contract));
+	assert_eq!(xc_regions.owner_of(Id::U128(0)), Some(charlie));
+	assert_eq!(xc_regions.regions.get(0), Some(region.clone()));
+	assert_eq!(xc_regions.attestation_nonce(Id::U128(0)), Ok(1));
+
+	let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+	assert_init_event(&emitted_events[emitted_events.len() - 2], 0, region.clone(), 0);
+	assert_attested_event(
+		&emitted_events.last().unwrap(),
+		0,
+		vec![relayer_key(&relayer_2), relayer_key(&relayer_1)],
+		0,
+	);
+
+	// 9. The same attestations can't be replayed once the region gets removed:
+	assert_ok!(xc_regions.remove(Id::U128(0)));
+	approve_contract(&mut xc_regions, region_id(0));
+	assert_eq!(
+		xc_regions.init_with_attestations(
+			Id::U128(0),
+			region.clone(),
+			0,
+			valid_until,
+			attestations
+		),
+		Err(XcRegionsError::InvalidNonce)
+	);
+
+	// 10. Attestations expire after `valid_until`:
+	(0..valid_until + 1).for_each(|_| advance_block::<DefaultEnvironment>());
+	assert_eq!(
+		xc_regions.init_with_attestations(
+			Id::U128(0),
+			region.clone(),
+			1,
+			valid_until,
+			vec![
+				attest(&relayer_1, 0, &region, 1, valid_until),
+				attest(&relayer_2, 0, &region, 1, valid_until)
+			]
+		),
+		Err(XcRegionsError::AttestationExpired)
+	);
+}
+
+#[ink::test]
+fn init_with_ed25519_attestations_works() {
+	let DefaultAccounts::<DefaultEnvironment> { alice, charlie, .. } = get_default_accounts();
+	set_caller::<DefaultEnvironment>(alice);
+	let mut xc_regions = XcRegions::new(REGIONS_COLLECTION_ID, Default::default());
+
+	let region = Region::default();
+	let valid_until = 10;
+	let sr25519_relayer = relayer_keypair(1);
+	let ed25519_relayer = SigningKey::from_bytes(&[2; 32]);
+
+	// Relayers with keys of either scheme can be registered:
+	assert_ok!(xc_regions.add_relayer(relayer_key(&sr25519_relayer)));
+	assert_ok!(xc_regions.add_relayer(ed25519_relayer_key(&ed25519_relayer)));
+	assert_ok!(xc_regions.set_attestation_threshold(2));
+
+	set_caller::<DefaultEnvironment>(charlie);
+	assert_ok!(xc_regions.mint(region_id(0), charlie));
+	approve_contract(&mut xc_regions, region_id(0));
+
+	// 1. The signature must be made with the scheme of the relayer's key:
+	let (_, signature) = attest(&sr25519_relayer, 0, &region, 0, valid_until);
+	assert_eq!(
+		xc_regions.init_with_attestations(
+			Id::U128(0),
+			region.clone(),
+			0,
+			valid_until,
+			vec![
+				attest(&sr25519_relayer, 0, &region, 0, valid_until),
+				(ed25519_relayer_key(&ed25519_relayer), signature)
+			]
+		),
+		Err(XcRegionsError::InvalidAttestation)
+	);
+
+	// 2. Works with a mix of sr25519 and ed25519 attestations:
+	assert_ok!(xc_regions.init_with_attestations(
+		Id::U128(0),
+		region.clone(),
+		0,
+		valid_until,
+		vec![
+			attest(&sr25519_relayer, 0, &region, 0, valid_until),
+			attest_ed25519(&ed25519_relayer, 0, &region, 0, valid_until)
+		]
+	));
+	assert_eq!(xc_regions.owner_of(Id::U128(0)), Some(charlie));
+	assert_eq!(xc_regions.regions.get(0), Some(region.clone()));
+
+	let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+	assert_attested_event(
+		&emitted_events.last().unwrap(),
+		0,
+		vec![relayer_key(&sr25519_relayer), ed25519_relayer_key(&ed25519_relayer)],
+		0,
+	);
+}
+
 #[ink::test]
 fn remove_works() {
 	let DefaultAccounts::<DefaultEnvironment> { bob, charlie, .. } = get_default_accounts();
-	let mut xc_regions = XcRegions::new();
+	let mut xc_regions = XcRegions::new(REGIONS_COLLECTION_ID, Default::default());
 	set_caller::<DefaultEnvironment>(charlie);
 
 	let contract = ink::env::account_id::<ink::env::DefaultEnvironment>();
@@ -149,6 +766,7 @@ fn remove_works() {
 
 	// Minting and initializing a region:
 	assert_ok!(xc_regions.mint(region_id(0), charlie));
+	approve_contract(&mut xc_regions, region_id(0));
 	assert_ok!(xc_regions.init(Id::U128(0), Region::default()));
 
 	// The region gets transferred to the contract:
@@ -185,63 +803,1387 @@ fn remove_works() {
 }
 
 #[ink::test]
-fn get_metadata_works() {
-	let DefaultAccounts::<DefaultEnvironment> { charlie, .. } = get_default_accounts();
-	let mut xc_regions = XcRegions::new();
+fn partition_works() {
+	let DefaultAccounts::<DefaultEnvironment> { bob, charlie, .. } = get_default_accounts();
+	let mut xc_regions = XcRegions::new(REGIONS_COLLECTION_ID, Default::default());
 	set_caller::<DefaultEnvironment>(charlie);
 
-	// Cannot get the metadata of a region that doesn't exist:
+	let region = Region { begin: 2, end: 10, core: 1, mask: CoreMask::complete() };
+	let raw_region_id = RawRegionId::from(region.id());
 
-	assert_eq!(xc_regions.get_metadata(Id::U128(0)), Err(XcRegionsError::MetadataNotFound));
+	assert_ok!(xc_regions.mint((REGIONS_COLLECTION_ID, raw_region_id), charlie));
+	approve_contract(&mut xc_regions, (REGIONS_COLLECTION_ID, raw_region_id));
+	assert_ok!(xc_regions.init(Id::U128(raw_region_id), region.clone()));
 
-	// Minting a region without initializing it.
-	assert_ok!(xc_regions.mint(region_id(0), charlie));
-	assert_eq!(xc_regions.get_metadata(Id::U128(0)), Err(XcRegionsError::MetadataNotFound));
+	// 1. Only the owner can partition the region:
+	set_caller::<DefaultEnvironment>(bob);
+	assert_eq!(xc_regions.partition(Id::U128(raw_region_id), 4), Err(XcRegionsError::NotOwner));
 
-	assert_ok!(xc_regions.init(Id::U128(0), Region::default()));
+	// 2. The pivot must be within the region:
+	set_caller::<DefaultEnvironment>(charlie);
 	assert_eq!(
-		xc_regions.get_metadata(Id::U128(0)),
+		xc_regions.partition(Id::U128(raw_region_id), 2),
+		Err(XcRegionsError::Region(RegionError::PivotTooEarly))
+	);
+	assert_eq!(
+		xc_regions.partition(Id::U128(raw_region_id), 10),
+		Err(XcRegionsError::Region(RegionError::PivotTooLate))
+	);
 
-		Ok(VersionedRegion { version: 0, region: Region::default() })
+	// 3. Pending regions can't be partitioned:
+	xc_regions.pending_regions.insert(
+		raw_region_id,
+		&PendingRegion { initializer: charlie, bond: 0, challenge_deadline: 10 },
 	);
-}
+	assert_eq!(
+		xc_regions.partition(Id::U128(raw_region_id), 4),
+		Err(XcRegionsError::RegionPending)
+	);
+	xc_regions.pending_regions.remove(raw_region_id);
 
-#[ink::test]
-fn metadata_version_gets_updated() {
-	let DefaultAccounts::<DefaultEnvironment> { charlie, .. } = get_default_accounts();
-	let mut xc_regions = XcRegions::new();
-	set_caller::<DefaultEnvironment>(charlie);
+	// 4. Partitioning works:
+	assert_ok!(xc_regions.partition(Id::U128(raw_region_id), 4));
 
-	assert_ok!(xc_regions.mint(region_id(0), charlie));
+	let first = Region { end: 4, ..region.clone() };
+	let second = Region { begin: 4, ..region.clone() };
+	let (first_id, second_id) = (RawRegionId::from(first.id()), RawRegionId::from(second.id()));
+
+	// The first region keeps the identifier of the partitioned region:
+	assert_eq!(first_id, raw_region_id);
+	assert_eq!(xc_regions.owner_of(Id::U128(first_id)), Some(charlie));
+	assert_eq!(xc_regions.owner_of(Id::U128(second_id)), Some(charlie));
+	assert_eq!(xc_regions.balance_of(charlie), 2);
 
-	assert_ok!(xc_regions.init(Id::U128(0), Region::default()));
 	assert_eq!(
-		xc_regions.get_metadata(Id::U128(0)),
-		Ok(VersionedRegion { version: 0, region: Region::default() })
+		xc_regions.get_metadata(Id::U128(first_id)),
+		Ok(VersionedRegion { version: 1, region: first })
+	);
+	assert_eq!(
+		xc_regions.get_metadata(Id::U128(second_id)),
+		Ok(VersionedRegion { version: 0, region: second.clone() })
 	);
 
-	assert_ok!(xc_regions.remove(Id::U128(0)));
+	let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+	assert_partitioned_event(
+		&emitted_events.last().unwrap(),
+		raw_region_id,
+		4,
+		((first_id, 1), (second_id, 0)),
+	);
 
-	assert_ok!(xc_regions.init(Id::U128(0), Region::default()));
+	// The underlying region stays in the contract, so the derived regions can't be removed:
+	assert_eq!(xc_regions.remove(Id::U128(first_id)), Err(XcRegionsError::DerivedRegion));
+	assert_eq!(xc_regions.remove(Id::U128(second_id)), Err(XcRegionsError::DerivedRegion));
+	assert_eq!(xc_regions.underlying_regions.get(first_id), Some(region.clone()));
+	assert_eq!(xc_regions.underlying_regions.get(second_id), Some(region.clone()));
+
+	// 5. Derived regions can be partitioned further:
+	assert_ok!(xc_regions.partition(Id::U128(second_id), 6));
+
+	let third = Region { begin: 6, ..second.clone() };
+	let third_id = RawRegionId::from(third.id());
 	assert_eq!(
-		xc_regions.get_metadata(Id::U128(0)),
-		Ok(VersionedRegion { version: 1, region: Region::default() })
+		xc_regions.get_metadata(Id::U128(second_id)),
+		Ok(VersionedRegion { version: 1, region: Region { end: 6, ..second } })
+	);
+	assert_eq!(
+		xc_regions.get_metadata(Id::U128(third_id)),
+		Ok(VersionedRegion { version: 0, region: third })
 	);
+	assert_eq!(xc_regions.underlying_regions.get(third_id), Some(region));
+	assert_eq!(xc_regions.balance_of(charlie), 3);
 }
 
-// Helper functions for test
-fn assert_init_event(
-	event: &ink::env::test::EmittedEvent,
-	expected_region_id: RawRegionId,
-	expected_metadata: Region,
-	expected_version: Version,
-) {
-	let decoded_event = <Event as scale::Decode>::decode(&mut &event.data[..])
-		.expect("encountered invalid contract event data buffer");
-	if let Event::RegionInitialized(RegionInitialized { region_id, metadata, version }) =
-		decoded_event
-	{
-		assert_eq!(
+#[ink::test]
+fn interlace_works() {
+	let DefaultAccounts::<DefaultEnvironment> { bob, charlie, .. } = get_default_accounts();
+	let mut xc_regions = XcRegions::new(REGIONS_COLLECTION_ID, Default::default());
+	set_caller::<DefaultEnvironment>(charlie);
+
+	let region = Region { begin: 2, end: 10, core: 1, mask: CoreMask::from_chunk(0, 40) };
+	let raw_region_id = RawRegionId::from(region.id());
+
+	assert_ok!(xc_regions.mint((REGIONS_COLLECTION_ID, raw_region_id), charlie));
+	approve_contract(&mut xc_regions, (REGIONS_COLLECTION_ID, raw_region_id));
+	assert_ok!(xc_regions.init(Id::U128(raw_region_id), region.clone()));
+
+	// 1. Only the owner can interlace the region:
+	set_caller::<DefaultEnvironment>(bob);
+	assert_eq!(
+		xc_regions.interlace(Id::U128(raw_region_id), CoreMask::from_chunk(0, 20)),
+		Err(XcRegionsError::NotOwner)
+	);
+
+	// 2. The pivot mask must be a non-empty strict subset of the region's mask:
+	set_caller::<DefaultEnvironment>(charlie);
+	assert_eq!(
+		xc_regions.interlace(Id::U128(raw_region_id), CoreMask::from_chunk(20, 60)),
+		Err(XcRegionsError::Region(RegionError::ExteriorPivot))
+	);
+	assert_eq!(
+		xc_regions.interlace(Id::U128(raw_region_id), CoreMask::void()),
+		Err(XcRegionsError::Region(RegionError::VoidPivot))
+	);
+	assert_eq!(
+		xc_regions.interlace(Id::U128(raw_region_id), CoreMask::from_chunk(0, 40)),
+		Err(XcRegionsError::Region(RegionError::CompletePivot))
+	);
+
+	// 3. Interlacing works:
+	assert_ok!(xc_regions.interlace(Id::U128(raw_region_id), CoreMask::from_chunk(0, 20)));
+
+	let first = Region { mask: CoreMask::from_chunk(0, 20), ..region.clone() };
+	let second = Region { mask: CoreMask::from_chunk(20, 40), ..region.clone() };
+	let (first_id, second_id) = (RawRegionId::from(first.id()), RawRegionId::from(second.id()));
+
+	// The interlaced region no longer exists:
+	assert_eq!(xc_regions.owner_of(Id::U128(raw_region_id)), None);
+	assert_eq!(
+		xc_regions.get_metadata(Id::U128(raw_region_id)),
+		Err(XcRegionsError::MetadataNotFound)
+	);
+
+	assert_eq!(xc_regions.owner_of(Id::U128(first_id)), Some(charlie));
+	assert_eq!(xc_regions.owner_of(Id::U128(second_id)), Some(charlie));
+	assert_eq!(
+		xc_regions.get_metadata(Id::U128(first_id)),
+		Ok(VersionedRegion { version: 0, region: first })
+	);
+	assert_eq!(
+		xc_regions.get_metadata(Id::U128(second_id)),
+		Ok(VersionedRegion { version: 0, region: second })
+	);
+
+	let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+	assert_interlaced_event(
+		&emitted_events.last().unwrap(),
+		raw_region_id,
+		CoreMask::from_chunk(0, 20),
+		((first_id, 0), (second_id, 0)),
+	);
+
+	// The resulting regions can't be removed until they are merged back:
+	assert_eq!(xc_regions.remove(Id::U128(first_id)), Err(XcRegionsError::DerivedRegion));
+	assert_eq!(xc_regions.underlying_regions.get(first_id), Some(region.clone()));
+	assert_eq!(xc_regions.underlying_regions.get(second_id), Some(region));
+}
+
+#[ink::test]
+fn merge_works() {
+	let DefaultAccounts::<DefaultEnvironment> { bob, charlie, .. } = get_default_accounts();
+	let mut xc_regions = XcRegions::new(REGIONS_COLLECTION_ID, Default::default());
+	set_caller::<DefaultEnvironment>(charlie);
+
+	let region = Region { begin: 2, end: 10, core: 1, mask: CoreMask::complete() };
+	let raw_region_id = RawRegionId::from(region.id());
+	let adjacent = Region { begin: 10, end: 12, core: 1, mask: CoreMask::complete() };
+	let adjacent_id = RawRegionId::from(adjacent.id());
+
+	assert_ok!(xc_regions.mint((REGIONS_COLLECTION_ID, raw_region_id), charlie));
+	approve_contract(&mut xc_regions, (REGIONS_COLLECTION_ID, raw_region_id));
+	assert_ok!(xc_regions.init(Id::U128(raw_region_id), region.clone()));
+	assert_ok!(xc_regions.mint((REGIONS_COLLECTION_ID, adjacent_id), charlie));
+	approve_contract(&mut xc_regions, (REGIONS_COLLECTION_ID, adjacent_id));
+	assert_ok!(xc_regions.init(Id::U128(adjacent_id), adjacent.clone()));
+
+	// 1. Regions with different underlying regions can't be merged:
+	assert_eq!(
+		xc_regions.merge(Id::U128(raw_region_id), Id::U128(adjacent_id)),
+		Err(XcRegionsError::DifferentUnderlying)
+	);
+
+	assert_ok!(xc_regions.partition(Id::U128(raw_region_id), 4));
+	assert_ok!(
+		xc_regions.partition(Id::U128(RawRegionId::from(RegionId { begin: 4, ..region.id() })), 6)
+	);
+
+	let first = Region { end: 4, ..region.clone() };
+	let second = Region { begin: 4, end: 6, ..region.clone() };
+	let third = Region { begin: 6, ..region.clone() };
+	let (first_id, second_id, third_id) = (
+		RawRegionId::from(first.id()),
+		RawRegionId::from(second.id()),
+		RawRegionId::from(third.id()),
+	);
+
+	// 2. Only the owner can merge the regions:
+	set_caller::<DefaultEnvironment>(bob);
+	assert_eq!(
+		xc_regions.merge(Id::U128(second_id), Id::U128(third_id)),
+		Err(XcRegionsError::NotOwner)
+	);
+
+	// 3. The regions must be time-adjacent:
+	set_caller::<DefaultEnvironment>(charlie);
+	assert_eq!(
+		xc_regions.merge(Id::U128(first_id), Id::U128(third_id)),
+		Err(XcRegionsError::Region(RegionError::NotMergeable))
+	);
+
+	// 4. Merging works:
+	assert_ok!(xc_regions.merge(Id::U128(third_id), Id::U128(second_id)));
+
+	let merged = Region { begin: 4, ..region.clone() };
+	assert_eq!(xc_regions.owner_of(Id::U128(third_id)), None);
+	assert_eq!(xc_regions.owner_of(Id::U128(second_id)), Some(charlie));
+	assert_eq!(
+		xc_regions.get_metadata(Id::U128(second_id)),
+		Ok(VersionedRegion { version: 2, region: merged })
+	);
+
+	let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+	assert_merged_event(&emitted_events.last().unwrap(), (third_id, second_id), second_id, 2);
+
+	// The merged region is still derived from the underlying region:
+	assert_eq!(xc_regions.remove(Id::U128(second_id)), Err(XcRegionsError::DerivedRegion));
+
+	// 5. Merging all the derived regions gives back the underlying region:
+	assert_ok!(xc_regions.merge(Id::U128(first_id), Id::U128(second_id)));
+
+	assert_eq!(
+		xc_regions.get_metadata(Id::U128(raw_region_id)),
+		Ok(VersionedRegion { version: 2, region })
+	);
+	assert_eq!(xc_regions.underlying_regions.get(raw_region_id), None);
+	assert_eq!(xc_regions.balance_of(charlie), 2);
+
+	// The region can be removed again:
+	assert_ok!(xc_regions.remove(Id::U128(raw_region_id)));
+}
+
+#[ink::test]
+fn remove_and_teleport_works() {
+	let DefaultAccounts::<DefaultEnvironment> { alice, bob, charlie, .. } = get_default_accounts();
+	set_caller::<DefaultEnvironment>(alice);
+	let mut xc_regions = XcRegions::new(REGIONS_COLLECTION_ID, Default::default());
+
+	let coretime_chain = MultiLocation::new(1, X1(Parachain(1005)));
+	let dest = VersionedMultiLocation::V3(coretime_chain);
+
+	set_caller::<DefaultEnvironment>(charlie);
+	assert_ok!(xc_regions.mint(region_id(0), charlie));
+	approve_contract(&mut xc_regions, region_id(0));
+	assert_ok!(xc_regions.init(Id::U128(0), Region::default()));
+
+	// 1. Sending regions back is disabled by default:
+	assert_eq!(
+		xc_regions.remove_and_teleport(Id::U128(0), dest.clone()),
+		Err(XcRegionsError::TeleportDisabled)
+	);
+
+	// 2. Only the owner of the contract can enable it:
+	let config = TeleportConfig {
+		indices: Default::default(),
+		coretime_para_id: 1005,
+		broker_pallet_index: 50,
+		fee: 1_000,
+		fee_asset: FeeAsset { indices: Default::default(), asset_id: 1 },
+		max_weight: (1_000_000_000, 100_000),
+	};
+	assert_eq!(
+		xc_regions.set_teleport_config(Some(config)),
+		Err(XcRegionsError::Ownable(OwnableError::CallerIsNotOwner))
+	);
+	set_caller::<DefaultEnvironment>(alice);
+	assert_ok!(xc_regions.set_teleport_config(Some(config)));
+	assert_eq!(xc_regions.teleport_config(), Some(config));
+
+	// 3. Only the owner of the region can send it back:
+	set_caller::<DefaultEnvironment>(bob);
+	assert_eq!(
+		xc_regions.remove_and_teleport(Id::U128(0), dest.clone()),
+		Err(XcRegionsError::CannotRemove)
+	);
+
+	// 4. The destination must be the configured Coretime chain:
+	set_caller::<DefaultEnvironment>(charlie);
+	let other_chain = MultiLocation::new(1, X1(Parachain(2000)));
+	assert_eq!(
+		xc_regions.remove_and_teleport(Id::U128(0), VersionedMultiLocation::V3(other_chain)),
+		Err(XcRegionsError::InvalidDestination)
+	);
+
+	// 5. The caller must approve the contract to spend the fee in relay chain tokens:
+	assert_eq!(
+		xc_regions.remove_and_teleport(Id::U128(0), dest.clone()),
+		Err(XcRegionsError::MissingFee)
+	);
+	xc_regions.approve_fee(charlie, 999);
+	assert_eq!(
+		xc_regions.remove_and_teleport(Id::U128(0), dest.clone()),
+		Err(XcRegionsError::MissingFee)
+	);
+	assert_eq!(xc_regions.owner_of(Id::U128(0)), Some(charlie));
+
+	// 6. Sending the region back works:
+	xc_regions.approve_fee(charlie, 1_000);
+	assert_ok!(xc_regions.remove_and_teleport(Id::U128(0), dest.clone()));
+	assert_eq!(xc_regions.collected_fees, 1_000);
+	assert_eq!(xc_regions.fee_allowances.get(charlie), Some(0));
+
+	// The wrapped region and its metadata are removed:
+	assert_eq!(xc_regions.owner_of(Id::U128(0)), None);
+	assert_eq!(xc_regions.get_metadata(Id::U128(0)), Err(XcRegionsError::MetadataNotFound));
+
+	// The underlying region leaves this chain, deposited to charlie on the Coretime chain:
+	assert!(!xc_regions._uniques_exists(0));
+	let message = reserve_withdraw_region(coretime_chain, 50, 0, 1_000, &charlie).unwrap();
+	assert_eq!(
+		xc_regions.executed_xcms.get(0),
+		Some(scale::Encode::encode(&VersionedXcm::V3(message)))
+	);
+
+	let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+	assert_teleported_event(&emitted_events.last().unwrap(), 0, charlie, dest);
+}
+
+#[ink::test]
+fn assign_works() {
+	let DefaultAccounts::<DefaultEnvironment> { alice, bob, charlie, .. } = get_default_accounts();
+	set_caller::<DefaultEnvironment>(alice);
+	let mut xc_regions = XcRegions::new(REGIONS_COLLECTION_ID, Default::default());
+
+	set_caller::<DefaultEnvironment>(charlie);
+	assert_ok!(xc_regions.mint(region_id(0), charlie));
+	approve_contract(&mut xc_regions, region_id(0));
+	assert_ok!(xc_regions.init(Id::U128(0), Region::default()));
+
+	// 1. Assigning regions is disabled by default:
+	assert_eq!(
+		xc_regions.assign(Id::U128(0), 2000, Finality::Provisional),
+		Err(XcRegionsError::TransactDisabled)
+	);
+
+	// 2. Only the owner of the contract can enable it:
+	let config = TransactConfig {
+		xcm_indices: Default::default(),
+		para_id: 2000,
+		coretime_para_id: 1005,
+		broker_indices: Default::default(),
+		coretime_xcm_indices: Default::default(),
+		fee: 1_000,
+		fee_asset: FeeAsset { indices: Default::default(), asset_id: 1 },
+		transact_weight: (1_000_000_000, 100_000),
+		max_weight: (1_000_000_000, 100_000),
+		reassignment_delay: 10,
+	};
+	assert_eq!(
+		xc_regions.set_transact_config(Some(config)),
+		Err(XcRegionsError::Ownable(OwnableError::CallerIsNotOwner))
+	);
+	set_caller::<DefaultEnvironment>(alice);
+	assert_ok!(xc_regions.set_transact_config(Some(config)));
+	assert_eq!(xc_regions.transact_config(), Some(config));
+
+	// 3. Only the owner of the region can assign it:
+	set_caller::<DefaultEnvironment>(bob);
+	assert_eq!(
+		xc_regions.assign(Id::U128(0), 2000, Finality::Provisional),
+		Err(XcRegionsError::NotOwner)
+	);
+
+	// 4. The caller must pay the fee for moving the region and for dispatching the call:
+	set_caller::<DefaultEnvironment>(charlie);
+	xc_regions.approve_fee(charlie, 1_999);
+	assert_eq!(
+		xc_regions.assign(Id::U128(0), 2000, Finality::Provisional),
+		Err(XcRegionsError::MissingFee)
+	);
+
+	// 5. Provisionally assigning the region works:
+	xc_regions.approve_fee(charlie, 2_000);
+	assert_ok!(xc_regions.assign(Id::U128(0), 2000, Finality::Provisional));
+	assert_eq!(xc_regions.collected_fees, 2_000);
+	assert_eq!(
+		xc_regions.assignment(Id::U128(0)),
+		Some(Assignment {
+			task: 2000,
+			finality: Finality::Provisional,
+			confirmed: false,
+			requested_at: 0
+		})
+	);
+
+	// The underlying region is first moved to the account of the contract on the Coretime chain:
+	let contract = ink::env::account_id::<DefaultEnvironment>();
+	assert!(!xc_regions._uniques_exists(0));
+	assert!(xc_regions.regions_on_coretime.contains(0));
+	let message = reserve_withdraw_region_to(
+		MultiLocation::new(1, X1(Parachain(1005))),
+		50,
+		0,
+		1_000,
+		sibling_account(2000, &contract),
+	)
+	.unwrap();
+	assert_eq!(
+		xc_regions.executed_xcms.get(0),
+		Some(scale::Encode::encode(&VersionedXcm::V3(message)))
+	);
+
+	// The assign call of pallet-broker is sent to the Coretime chain:
+	let expected_xcm = |task, finality| {
+		let call = RuntimeCall::Broker(
+			Default::default(),
+			BrokerCall::Assign { region_id: RegionId::from(0), task, finality },
+		);
+		let message = transact_on_coretime(
+			scale::Encode::encode(&call),
+			1_000,
+			Weight::from_parts(1_000_000_000, 100_000),
+		);
+		scale::Encode::encode(&VersionedXcm::V3(message))
+	};
+	assert_eq!(xc_regions.sent_xcms.get(), Some(vec![expected_xcm(2000, Finality::Provisional)]));
+
+	let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+	assert_assignment_requested_event(
+		&emitted_events.last().unwrap(),
+		0,
+		2000,
+		Finality::Provisional,
+	);
+
+	// 6. Only the owner of the contract can confirm the assignment:
+	assert_eq!(
+		xc_regions.confirm_assignment(Id::U128(0), true),
+		Err(XcRegionsError::Ownable(OwnableError::CallerIsNotOwner))
+	);
+	set_caller::<DefaultEnvironment>(alice);
+	assert_ok!(xc_regions.confirm_assignment(Id::U128(0), true));
+	assert_eq!(
+		xc_regions.assignment(Id::U128(0)),
+		Some(Assignment {
+			task: 2000,
+			finality: Finality::Provisional,
+			confirmed: true,
+			requested_at: 0
+		})
+	);
+	assert_eq!(
+		xc_regions.confirm_assignment(Id::U128(0), true),
+		Err(XcRegionsError::AssignmentNotFound)
+	);
+
+	let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+	assert_assigned_event(&emitted_events.last().unwrap(), 0, 2000, Finality::Provisional);
+
+	// The region can no longer be removed, since it was moved to the Coretime chain:
+	set_caller::<DefaultEnvironment>(charlie);
+	assert_eq!(xc_regions.remove(Id::U128(0)), Err(XcRegionsError::RegionOnCoretime));
+
+	// Provisionally assigned regions can be transferred and reassigned once the reassignment
+	// delay passed, without moving the region again:
+	assert_ok!(xc_regions.transfer(bob, Id::U128(0), vec![]));
+	set_caller::<DefaultEnvironment>(bob);
+	xc_regions.approve_fee(bob, 10_000);
+	assert_eq!(
+		xc_regions.assign(Id::U128(0), 2001, Finality::Final),
+		Err(XcRegionsError::ReassignmentTooSoon)
+	);
+	(0..10).for_each(|_| advance_block::<DefaultEnvironment>());
+	assert_ok!(xc_regions.assign(Id::U128(0), 2001, Finality::Final));
+	assert_eq!(xc_regions.collected_fees, 3_000);
+	assert_eq!(
+		xc_regions.sent_xcms.get(),
+		Some(vec![expected_xcm(2000, Finality::Provisional), expected_xcm(2001, Finality::Final)])
+	);
+
+	// 7. A failed assignment is removed once its outcome is known:
+	set_caller::<DefaultEnvironment>(alice);
+	assert_ok!(xc_regions.confirm_assignment(Id::U128(0), false));
+	assert_eq!(xc_regions.assignment(Id::U128(0)), None);
+
+	// 8. Unless it is finally assigned, the region can be sent back from the Coretime chain by
+	// the owner of the region, who pays the fee for both chains:
+	set_caller::<DefaultEnvironment>(charlie);
+	assert_eq!(xc_regions.retrieve_from_coretime(Id::U128(0)), Err(XcRegionsError::NotOwner));
+	set_caller::<DefaultEnvironment>(bob);
+	assert_ok!(xc_regions.retrieve_from_coretime(Id::U128(0)));
+	assert_eq!(xc_regions.collected_fees, 5_000);
+
+	let call = RuntimeCall::Xcm(
+		Default::default(),
+		XcmCall::Execute {
+			message: VersionedXcm::V3(reserve_transfer_region(50, 0, 1_000, 2000, &contract)),
+			max_weight: Weight::from_parts(1_000_000_000, 100_000),
+		},
+	);
+	let message = transact_on_coretime(
+		scale::Encode::encode(&call),
+		1_000,
+		Weight::from_parts(1_000_000_000, 100_000),
+	);
+	assert_eq!(
+		xc_regions.sent_xcms.get().unwrap().last(),
+		Some(&scale::Encode::encode(&VersionedXcm::V3(message)))
+	);
+
+	let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+	assert_retrieval_requested_event(&emitted_events.last().unwrap(), 0);
+
+	// The region can't be removed until it arrives:
+	assert_eq!(xc_regions.remove(Id::U128(0)), Err(XcRegionsError::RegionOnCoretime));
+	assert_ok!(xc_regions.mint(region_id(0), contract));
+	assert_eq!(
+		xc_regions.retrieve_from_coretime(Id::U128(0)),
+		Err(XcRegionsError::RegionNotOnCoretime)
+	);
+
+	// 9. Finally assigned regions can't be transferred, reassigned, sent back, split or removed.
+	// The region is moved to the Coretime chain again before being assigned:
+	assert_ok!(xc_regions.assign(Id::U128(0), 2001, Finality::Final));
+	assert!(!xc_regions._uniques_exists(0));
+	assert!(xc_regions.is_finally_assigned(Id::U128(0)));
+	assert_eq!(
+		xc_regions.transfer(charlie, Id::U128(0), vec![]),
+		Err(PSP34Error::Custom(String::from("RegionAssigned")))
+	);
+	assert_eq!(
+		xc_regions.assign(Id::U128(0), 2002, Finality::Final),
+		Err(XcRegionsError::RegionAssigned)
+	);
+	assert_eq!(xc_regions.retrieve_from_coretime(Id::U128(0)), Err(XcRegionsError::RegionAssigned));
+	assert_eq!(xc_regions.partition(Id::U128(0), 1), Err(XcRegionsError::RegionAssigned));
+	assert_eq!(xc_regions.remove(Id::U128(0)), Err(XcRegionsError::RegionAssigned));
+	assert_eq!(
+		xc_regions.remove_batch(vec![Id::U128(0)]),
+		Err(XcRegionsError::BatchFailed(vec![(0, XcRegionsError::RegionAssigned)]))
+	);
+
+	// 10. Regions derived within the contract can't be assigned:
+	let region = Region { begin: 2, end: 10, core: 1, mask: CoreMask::complete() };
+	let raw_region_id = RawRegionId::from(region.id());
+	assert_ok!(xc_regions.mint((REGIONS_COLLECTION_ID, raw_region_id), bob));
+	approve_contract(&mut xc_regions, (REGIONS_COLLECTION_ID, raw_region_id));
+	assert_ok!(xc_regions.init(Id::U128(raw_region_id), region));
+	assert_ok!(xc_regions.partition(Id::U128(raw_region_id), 4));
+	assert_eq!(
+		xc_regions.assign(Id::U128(raw_region_id), 2000, Finality::Final),
+		Err(XcRegionsError::DerivedRegion)
+	);
+}
+
+#[ink::test]
+fn pool_works() {
+	let DefaultAccounts::<DefaultEnvironment> { alice, bob, charlie, django, .. } =
+		get_default_accounts();
+	set_caller::<DefaultEnvironment>(alice);
+	let mut xc_regions = XcRegions::new(REGIONS_COLLECTION_ID, Default::default());
+
+	let config = TransactConfig {
+		xcm_indices: Default::default(),
+		para_id: 2000,
+		coretime_para_id: 1005,
+		broker_indices: Default::default(),
+		coretime_xcm_indices: Default::default(),
+		fee: 1_000,
+		fee_asset: FeeAsset { indices: Default::default(), asset_id: 1 },
+		transact_weight: (1_000_000_000, 100_000),
+		max_weight: (1_000_000_000, 100_000),
+		reassignment_delay: 10,
+	};
+	assert_ok!(xc_regions.set_transact_config(Some(config)));
+	assert_ok!(xc_regions.set_timeslice_period(1));
+
+	let region = Region { begin: 2, end: 10, core: 1, mask: CoreMask::complete() };
+	let raw_region_id = RawRegionId::from(region.id());
+
+	set_caller::<DefaultEnvironment>(charlie);
+	assert_ok!(xc_regions.mint((REGIONS_COLLECTION_ID, raw_region_id), charlie));
+	approve_contract(&mut xc_regions, (REGIONS_COLLECTION_ID, raw_region_id));
+	assert_ok!(xc_regions.init(Id::U128(raw_region_id), region.clone()));
+	xc_regions.approve_fee(charlie, 100_000);
+
+	let expected_xcm = |call| {
+		let call = RuntimeCall::Broker(Default::default(), call);
+		let message = transact_on_coretime(
+			scale::Encode::encode(&call),
+			1_000,
+			Weight::from_parts(1_000_000_000, 100_000),
+		);
+		scale::Encode::encode(&VersionedXcm::V3(message))
+	};
+
+	// 1. Only the owner of the region can pool it:
+	set_caller::<DefaultEnvironment>(bob);
+	assert_eq!(xc_regions.pool(Id::U128(raw_region_id)), Err(XcRegionsError::NotOwner));
+
+	// 2. Regions owned by a contract can't be pooled, since the contract wouldn't be able to
+	// access the revenue:
+	set_caller::<DefaultEnvironment>(charlie);
+	assert_ok!(xc_regions.transfer(django, Id::U128(raw_region_id), vec![]));
+	ink::env::test::set_contract::<DefaultEnvironment>(django);
+	set_caller::<DefaultEnvironment>(django);
+	assert_eq!(xc_regions.pool(Id::U128(raw_region_id)), Err(XcRegionsError::OwnedByContract));
+	assert_ok!(xc_regions.transfer(charlie, Id::U128(raw_region_id), vec![]));
+
+	// 3. Pooling works, replacing a provisional assignment. The revenue is paid to the owner:
+	set_caller::<DefaultEnvironment>(charlie);
+	assert_ok!(xc_regions.assign(Id::U128(raw_region_id), 2000, Finality::Provisional));
+	assert_ok!(xc_regions.pool(Id::U128(raw_region_id)));
+	// The region was already moved when assigning it:
+	assert_eq!(xc_regions.collected_fees, 3_000);
+
+	assert_eq!(xc_regions.assignment(Id::U128(raw_region_id)), None);
+	assert_eq!(
+		xc_regions.pooled_regions.get(raw_region_id),
+		Some(PooledRegion { payee: charlie, claimed_until: 2, pending_claim: None })
+	);
+	assert_eq!(
+		xc_regions.sent_xcms.get().unwrap().last(),
+		Some(&expected_xcm(BrokerCall::Pool {
+			region_id: region.id(),
+			payee: charlie,
+			finality: Finality::Final
+		}))
+	);
+
+	let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+	assert_pooled_event(&emitted_events.last().unwrap(), raw_region_id, charlie);
+
+	// 4. Pooled regions can't be transferred, pooled again or removed:
+	assert_eq!(
+		xc_regions.transfer(bob, Id::U128(raw_region_id), vec![]),
+		Err(PSP34Error::Custom(String::from("RegionPooled")))
+	);
+	assert_eq!(xc_regions.pool(Id::U128(raw_region_id)), Err(XcRegionsError::RegionPooled));
+	assert_eq!(xc_regions.remove(Id::U128(raw_region_id)), Err(XcRegionsError::RegionPooled));
+
+	// 5. Only the owner of the region can claim its revenue:
+	set_caller::<DefaultEnvironment>(bob);
+	assert_eq!(xc_regions.claim_revenue(Id::U128(raw_region_id), 3), Err(XcRegionsError::NotOwner));
+
+	// 6. Revenue can only be claimed for the timeslices that are over:
+	set_caller::<DefaultEnvironment>(charlie);
+	assert_eq!(
+		xc_regions.claim_revenue(Id::U128(raw_region_id), 3),
+		Err(XcRegionsError::NoRevenueToClaim)
+	);
+
+	// 7. Claiming the revenue works, and the claim awaits confirmation:
+	(0..4).for_each(|_| advance_block::<DefaultEnvironment>());
+	assert_ok!(xc_regions.claim_revenue(Id::U128(raw_region_id), 3));
+	assert_eq!(
+		xc_regions.sent_xcms.get().unwrap().last(),
+		Some(&expected_xcm(BrokerCall::ClaimRevenue { region_id: region.id(), max_timeslices: 2 }))
+	);
+	assert_eq!(
+		xc_regions.pooled_regions.get(raw_region_id),
+		Some(PooledRegion { payee: charlie, claimed_until: 2, pending_claim: Some(4) })
+	);
+
+	let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+	assert_revenue_claim_requested_event(&emitted_events.last().unwrap(), raw_region_id, (2, 4));
+
+	// Only one claim can be pending at a time:
+	assert_eq!(
+		xc_regions.claim_revenue(Id::U128(raw_region_id), 3),
+		Err(XcRegionsError::ClaimPending)
+	);
+
+	// 8. Only the owner of the contract can confirm the claim, within the claimed timeslices:
+	assert_eq!(
+		xc_regions.confirm_revenue_claim(Id::U128(raw_region_id), 3),
+		Err(XcRegionsError::Ownable(OwnableError::CallerIsNotOwner))
+	);
+	set_caller::<DefaultEnvironment>(alice);
+	assert_eq!(
+		xc_regions.confirm_revenue_claim(Id::U128(raw_region_id), 5),
+		Err(XcRegionsError::InvalidClaim)
+	);
+
+	// pallet-broker stopped at the first timeslice whose revenue wasn't known yet:
+	assert_ok!(xc_regions.confirm_revenue_claim(Id::U128(raw_region_id), 3));
+	assert_eq!(
+		xc_regions.pooled_regions.get(raw_region_id),
+		Some(PooledRegion { payee: charlie, claimed_until: 3, pending_claim: None })
+	);
+	assert_eq!(
+		xc_regions.confirm_revenue_claim(Id::U128(raw_region_id), 3),
+		Err(XcRegionsError::ClaimNotFound)
+	);
+
+	let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+	assert_revenue_claimed_event(&emitted_events.last().unwrap(), raw_region_id, (2, 3));
+
+	// The next claim continues from the contribution re-keyed by pallet-broker:
+	(0..8).for_each(|_| advance_block::<DefaultEnvironment>());
+	set_caller::<DefaultEnvironment>(charlie);
+	assert_ok!(xc_regions.claim_revenue(Id::U128(raw_region_id), 10));
+	assert_eq!(
+		xc_regions.sent_xcms.get().unwrap().last(),
+		Some(&expected_xcm(BrokerCall::ClaimRevenue {
+			region_id: RegionId { begin: 3, ..region.id() },
+			max_timeslices: 7
+		}))
+	);
+
+	// The wrapped region isn't burned before the claim is confirmed:
+	assert_eq!(xc_regions.owner_of(Id::U128(raw_region_id)), Some(charlie));
+
+	// 9. Once all the revenue is claimed the wrapped region is burned:
+	set_caller::<DefaultEnvironment>(alice);
+	assert_ok!(xc_regions.confirm_revenue_claim(Id::U128(raw_region_id), 10));
+
+	let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+	assert_revenue_claimed_event(&emitted_events[emitted_events.len() - 3], raw_region_id, (3, 10));
+	assert_transferred_event(
+		&emitted_events[emitted_events.len() - 2],
+		raw_region_id,
+		Some(charlie),
+		None,
+		VersionedRegion { version: 0, region: region.clone() },
+	);
+	assert_removed_event(&emitted_events.last().unwrap(), raw_region_id);
+
+	assert_eq!(xc_regions.pooled_regions.get(raw_region_id), None);
+	assert_eq!(xc_regions.owner_of(Id::U128(raw_region_id)), None);
+	assert_eq!(
+		xc_regions.get_metadata(Id::U128(raw_region_id)),
+		Err(XcRegionsError::MetadataNotFound)
+	);
+	set_caller::<DefaultEnvironment>(charlie);
+	assert_eq!(
+		xc_regions.claim_revenue(Id::U128(raw_region_id), 1),
+		Err(XcRegionsError::MetadataNotFound)
+	);
+}
+
+#[ink::test]
+fn init_batch_works() {
+	let DefaultAccounts::<DefaultEnvironment> { bob, charlie, .. } = get_default_accounts();
+	let mut xc_regions = XcRegions::new(REGIONS_COLLECTION_ID, Default::default());
+	set_caller::<DefaultEnvironment>(charlie);
+
+	let batch: Vec<_> = (0..3)
+		.map(|core| {
+			let region = Region { begin: 2, end: 10, core, mask: CoreMask::complete() };
+			(Id::U128(RawRegionId::from(region.id())), region)
+		})
+		.collect();
+
+	for (index, (id, _)) in batch.iter().enumerate() {
+		let Id::U128(raw_region_id) = id else { unreachable!() };
+		let owner = if index == 0 { bob } else { charlie };
+		assert_ok!(xc_regions.mint((REGIONS_COLLECTION_ID, *raw_region_id), owner));
+		approve_contract(&mut xc_regions, (REGIONS_COLLECTION_ID, *raw_region_id));
+	}
+
+	// 1. The batch size is bounded:
+	let too_large = vec![(Id::U128(0), Region::default()); MAX_BATCH_SIZE + 1];
+	assert_eq!(xc_regions.init_batch(too_large), Err(XcRegionsError::BatchTooLarge));
+
+	// 2. The errors of all the failed items are reported:
+	assert_eq!(
+		xc_regions.init_batch(vec![batch[0].clone(), (batch[1].0.clone(), Region::default())]),
+		Err(XcRegionsError::BatchFailed(vec![
+			(0, XcRegionsError::CannotInitialize),
+			(1, XcRegionsError::InvalidMetadata),
+		]))
+	);
+
+	// 3. Initializing a batch works:
+	assert_ok!(xc_regions.init_batch(batch[1..].to_vec()));
+
+	for (id, region) in batch[1..].iter().cloned() {
+		assert_eq!(xc_regions.owner_of(id.clone()), Some(charlie));
+		assert_eq!(xc_regions.get_metadata(id), Ok(VersionedRegion { version: 0, region }));
+	}
+	assert_eq!(xc_regions.balance_of(charlie), 2);
+}
+
+#[ink::test]
+fn remove_batch_works() {
+	let DefaultAccounts::<DefaultEnvironment> { charlie, .. } = get_default_accounts();
+	let mut xc_regions = XcRegions::new(REGIONS_COLLECTION_ID, Default::default());
+	set_caller::<DefaultEnvironment>(charlie);
+
+	let ids: Vec<_> = (0..3)
+		.map(|core| {
+			let region = Region { begin: 2, end: 10, core, mask: CoreMask::complete() };
+			let raw_region_id = RawRegionId::from(region.id());
+			assert_ok!(xc_regions.mint((REGIONS_COLLECTION_ID, raw_region_id), charlie));
+			approve_contract(&mut xc_regions, (REGIONS_COLLECTION_ID, raw_region_id));
+			assert_ok!(xc_regions.init(Id::U128(raw_region_id), region));
+			Id::U128(raw_region_id)
+		})
+		.collect();
+
+	// 1. The batch size is bounded:
+	assert_eq!(
+		xc_regions.remove_batch(vec![Id::U128(0); MAX_BATCH_SIZE + 1]),
+		Err(XcRegionsError::BatchTooLarge)
+	);
+
+	// 2. The errors of all the failed items are reported:
+	assert_eq!(
+		xc_regions.remove_batch(vec![Id::U128(0), Id::U8(1)]),
+		Err(XcRegionsError::BatchFailed(vec![
+			(0, XcRegionsError::CannotRemove),
+			(1, XcRegionsError::InvalidRegionId),
+		]))
+	);
+
+	// 3. Removing a batch works:
+	assert_ok!(xc_regions.remove_batch(ids.clone()));
+
+	for id in ids {
+		let Id::U128(raw_region_id) = id else { unreachable!() };
+		assert_eq!(xc_regions.owner_of(id), None);
+		assert_eq!(xc_regions._uniques_owner(raw_region_id), Some(charlie));
+	}
+	assert_eq!(xc_regions.balance_of(charlie), 0);
+}
+
+#[ink::test]
+fn reap_expired_works() {
+	let DefaultAccounts::<DefaultEnvironment> { alice, bob, charlie, django, .. } =
+		get_default_accounts();
+	set_caller::<DefaultEnvironment>(alice);
+	let mut xc_regions = XcRegions::new(REGIONS_COLLECTION_ID, Default::default());
+	let contract = ink::env::account_id::<DefaultEnvironment>();
+
+	set_caller::<DefaultEnvironment>(charlie);
+	let ids: Vec<_> = [2, 5]
+		.into_iter()
+		.enumerate()
+		.map(|(core, end)| {
+			let region = Region { begin: 0, end, core: core as u16, mask: CoreMask::complete() };
+			let raw_region_id = RawRegionId::from(region.id());
+			assert_ok!(xc_regions.mint((REGIONS_COLLECTION_ID, raw_region_id), charlie));
+			approve_contract(&mut xc_regions, (REGIONS_COLLECTION_ID, raw_region_id));
+			assert_ok!(xc_regions.init(Id::U128(raw_region_id), region));
+			raw_region_id
+		})
+		.collect();
+
+	// 1. Reaping is disabled by default:
+	set_caller::<DefaultEnvironment>(bob);
+	assert_eq!(xc_regions.reap_expired(vec![Id::U128(ids[0])]), Err(XcRegionsError::ReapDisabled));
+
+	set_caller::<DefaultEnvironment>(alice);
+	assert_ok!(xc_regions.set_reap_config(Some(ReapConfig { reward: 10 })));
+	assert_ok!(xc_regions.set_timeslice_period(2));
+	set_value_transferred::<DefaultEnvironment>(15);
+	xc_regions.fund_reap_reward_pool();
+	assert_eq!(xc_regions.reap_reward_pool(), 15);
+	set_account_balance::<DefaultEnvironment>(contract, 15);
+
+	// 2. Regions can't be reaped before they expire:
+	set_caller::<DefaultEnvironment>(bob);
+	assert_eq!(
+		xc_regions.reap_expired(vec![Id::U128(ids[0])]),
+		Err(XcRegionsError::BatchFailed(vec![(0, XcRegionsError::RegionNotExpired)]))
+	);
+
+	// 3. Reaping an expired region works and rewards the caller:
+	(0..4).for_each(|_| advance_block::<DefaultEnvironment>());
+	let bob_balance = get_account_balance::<DefaultEnvironment>(bob).unwrap();
+
+	assert_ok!(xc_regions.reap_expired(vec![Id::U128(ids[0])]));
+	assert_eq!(xc_regions.owner_of(Id::U128(ids[0])), None);
+	assert_eq!(xc_regions.regions.get(ids[0]), None);
+	// The metadata version is kept for the case the region gets re-initialized:
+	assert_eq!(xc_regions.metadata_versions.get(ids[0]), Some(0));
+	assert_eq!(xc_regions._uniques_owner(ids[0]), Some(charlie));
+	assert_eq!(xc_regions.balance_of(charlie), 1);
+
+	assert_eq!(get_account_balance::<DefaultEnvironment>(bob), Ok(bob_balance + 10));
+	assert_eq!(xc_regions.reap_reward_pool(), 5);
+
+	let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+	assert_reaped_event(&emitted_events.last().unwrap(), ids[0], bob);
+
+	// 4. The reward is limited by the funds left in the pool:
+	assert_eq!(
+		xc_regions.reap_expired(vec![Id::U128(ids[1])]),
+		Err(XcRegionsError::BatchFailed(vec![(0, XcRegionsError::RegionNotExpired)]))
+	);
+	(0..6).for_each(|_| advance_block::<DefaultEnvironment>());
+
+	assert_ok!(xc_regions.reap_expired(vec![Id::U128(ids[1])]));
+	assert_eq!(xc_regions._uniques_owner(ids[1]), Some(charlie));
+	assert_eq!(xc_regions.balance_of(charlie), 0);
+
+	assert_eq!(get_account_balance::<DefaultEnvironment>(bob), Ok(bob_balance + 15));
+	assert_eq!(xc_regions.reap_reward_pool(), 0);
+
+	// 5. Regions owned by a contract can't be reaped:
+	let region = Region { begin: 0, end: 2, core: 2, mask: CoreMask::complete() };
+	let raw_region_id = RawRegionId::from(region.id());
+	set_caller::<DefaultEnvironment>(charlie);
+	assert_ok!(xc_regions.mint((REGIONS_COLLECTION_ID, raw_region_id), charlie));
+	approve_contract(&mut xc_regions, (REGIONS_COLLECTION_ID, raw_region_id));
+	assert_ok!(xc_regions.init(Id::U128(raw_region_id), region));
+	assert_ok!(xc_regions.transfer(django, Id::U128(raw_region_id), vec![]));
+	ink::env::test::set_contract::<DefaultEnvironment>(django);
+
+	set_caller::<DefaultEnvironment>(bob);
+	assert_eq!(
+		xc_regions.reap_expired(vec![Id::U128(raw_region_id)]),
+		Err(XcRegionsError::BatchFailed(vec![(0, XcRegionsError::OwnedByContract)]))
+	);
+	assert_eq!(xc_regions.owner_of(Id::U128(raw_region_id)), Some(django));
+}
+
+#[ink::test]
+fn reaping_derived_regions_releases_underlying_region() {
+	let DefaultAccounts::<DefaultEnvironment> { alice, bob, charlie, .. } = get_default_accounts();
+	set_caller::<DefaultEnvironment>(alice);
+	let mut xc_regions = XcRegions::new(REGIONS_COLLECTION_ID, Default::default());
+	let contract = ink::env::account_id::<DefaultEnvironment>();
+	assert_ok!(xc_regions.set_reap_config(Some(ReapConfig { reward: 0 })));
+	assert_ok!(xc_regions.set_timeslice_period(1));
+
+	let region = Region { begin: 0, end: 4, core: 0, mask: CoreMask::complete() };
+	let raw_region_id = RawRegionId::from(region.id());
+
+	set_caller::<DefaultEnvironment>(charlie);
+	assert_ok!(xc_regions.mint((REGIONS_COLLECTION_ID, raw_region_id), charlie));
+	approve_contract(&mut xc_regions, (REGIONS_COLLECTION_ID, raw_region_id));
+	assert_ok!(xc_regions.init(Id::U128(raw_region_id), region.clone()));
+
+	assert_ok!(xc_regions.partition(Id::U128(raw_region_id), 2));
+	let second_id = RawRegionId::from(RegionId { begin: 2, core: 0, mask: CoreMask::complete() });
+	assert_ok!(xc_regions.partition(Id::U128(second_id), 3));
+	assert_eq!(xc_regions.derived_pieces.get(raw_region_id), Some(3));
+
+	// Merging pieces back keeps track of the remaining pieces:
+	let third_id = RawRegionId::from(RegionId { begin: 3, core: 0, mask: CoreMask::complete() });
+	assert_ok!(xc_regions.merge(Id::U128(second_id), Id::U128(third_id)));
+	assert_eq!(xc_regions.derived_pieces.get(raw_region_id), Some(2));
+
+	// The derived regions can't be removed:
+	assert_eq!(xc_regions.remove(Id::U128(second_id)), Err(XcRegionsError::DerivedRegion));
+
+	(0..4).for_each(|_| advance_block::<DefaultEnvironment>());
+
+	// 1. The underlying region stays in the contract until the last derived region is reaped:
+	set_caller::<DefaultEnvironment>(bob);
+	assert_ok!(xc_regions.reap_expired(vec![Id::U128(raw_region_id)]));
+	assert_eq!(xc_regions._uniques_owner(raw_region_id), Some(contract));
+	assert_eq!(xc_regions.derived_pieces.get(raw_region_id), Some(1));
+
+	// 2. Reaping the last derived region releases the underlying region:
+	assert_ok!(xc_regions.reap_expired(vec![Id::U128(second_id)]));
+	assert_eq!(xc_regions._uniques_owner(raw_region_id), Some(charlie));
+	assert_eq!(xc_regions.derived_pieces.get(raw_region_id), None);
+	assert_eq!(xc_regions.underlying_regions.get(second_id), None);
+	assert_eq!(xc_regions.balance_of(charlie), 0);
+}
+
+#[ink::test]
+fn regions_of_works() {
+	let DefaultAccounts::<DefaultEnvironment> { alice, bob, charlie, .. } = get_default_accounts();
+	set_caller::<DefaultEnvironment>(alice);
+	let mut xc_regions = XcRegions::new(REGIONS_COLLECTION_ID, Default::default());
+	assert_ok!(xc_regions.set_timeslice_period(1));
+
+	set_caller::<DefaultEnvironment>(charlie);
+	let regions: Vec<_> = [(0, 4, 0), (4, 8, 1), (0, 2, 1)]
+		.into_iter()
+		.map(|(begin, end, core)| {
+			let region = Region { begin, end, core, mask: CoreMask::complete() };
+			let raw_region_id = RawRegionId::from(region.id());
+			assert_ok!(xc_regions.mint((REGIONS_COLLECTION_ID, raw_region_id), charlie));
+			approve_contract(&mut xc_regions, (REGIONS_COLLECTION_ID, raw_region_id));
+			assert_ok!(xc_regions.init(Id::U128(raw_region_id), region.clone()));
+			(Id::U128(raw_region_id), VersionedRegion { version: 0, region })
+		})
+		.collect();
+
+	// At timeslice 3 the first region is active, the second inactive and the third expired.
+	(0..3).for_each(|_| advance_block::<DefaultEnvironment>());
+
+	let query =
+		|filter: RegionFilter, cursor, limit| xc_regions.regions_of(charlie, filter, cursor, limit);
+	let page = |indices: &[usize], next| RegionsPage {
+		regions: indices.iter().map(|&i| regions[i].clone()).collect(),
+		next,
+	};
+
+	// 1. Querying works with pagination:
+	assert_eq!(query(Default::default(), 0, 2), page(&[0, 1], Some(2)));
+	assert_eq!(query(Default::default(), 2, 2), page(&[2], None));
+	assert_eq!(query(Default::default(), 0, u32::MAX), page(&[0, 1, 2], None));
+	assert_eq!(xc_regions.regions_of(bob, Default::default(), 0, 2), page(&[], None));
+
+	// A zero limit is treated as the maximum page size:
+	assert_eq!(query(Default::default(), 0, 0), page(&[0, 1, 2], None));
+
+	// 2. Filtering by core works:
+	assert_eq!(
+		query(RegionFilter { core: Some(1), ..Default::default() }, 0, 10),
+		page(&[1, 2], None)
+	);
+
+	// 3. Filtering by status works:
+	let status = |status| RegionFilter { status: Some(status), ..Default::default() };
+	assert_eq!(query(status(RegionStatus::Active), 0, 10), page(&[0], None));
+	assert_eq!(query(status(RegionStatus::Inactive), 0, 10), page(&[1], None));
+	assert_eq!(query(status(RegionStatus::Expired), 0, 10), page(&[2], None));
+
+	// 4. Filtering by the begin/end window works:
+	assert_eq!(
+		query(RegionFilter { min_begin: Some(4), ..Default::default() }, 0, 10),
+		page(&[1], None)
+	);
+	assert_eq!(
+		query(RegionFilter { max_end: Some(4), ..Default::default() }, 0, 10),
+		page(&[0, 2], None)
+	);
+
+	// 5. The number of regions scanned per page is bounded:
+	for raw_region_id in 0..MAX_PAGE_SIZE {
+		assert_ok!(xc_regions
+			.mint((REGIONS_COLLECTION_ID, 1_000 + RawRegionId::from(raw_region_id)), charlie));
+	}
+	let query =
+		|filter: RegionFilter, cursor, limit| xc_regions.regions_of(charlie, filter, cursor, limit);
+	let max_page_size = u128::from(MAX_PAGE_SIZE);
+	assert_eq!(query(Default::default(), 0, 10), page(&[0, 1, 2], Some(max_page_size)));
+	assert_eq!(query(Default::default(), max_page_size, 10), page(&[], None));
+}
+
+#[ink::test]
+fn get_metadata_works() {
+	let DefaultAccounts::<DefaultEnvironment> { charlie, .. } = get_default_accounts();
+	let mut xc_regions = XcRegions::new(REGIONS_COLLECTION_ID, Default::default());
+	set_caller::<DefaultEnvironment>(charlie);
+
+	// Cannot get the metadata of a region that doesn't exist:
+
+	assert_eq!(xc_regions.get_metadata(Id::U128(0)), Err(XcRegionsError::MetadataNotFound));
+
+	// Minting a region without initializing it.
+	assert_ok!(xc_regions.mint(region_id(0), charlie));
+	approve_contract(&mut xc_regions, region_id(0));
+	assert_eq!(xc_regions.get_metadata(Id::U128(0)), Err(XcRegionsError::MetadataNotFound));
+
+	assert_ok!(xc_regions.init(Id::U128(0), Region::default()));
+	assert_eq!(
+		xc_regions.get_metadata(Id::U128(0)),
+
+		Ok(VersionedRegion { version: 0, region: Region::default() })
+	);
+}
+
+#[ink::test]
+fn init_respects_underlying_item_state() {
+	let DefaultAccounts::<DefaultEnvironment> { bob, charlie, .. } = get_default_accounts();
+	let mut xc_regions = XcRegions::new(REGIONS_COLLECTION_ID, Default::default());
+	let contract = ink::env::account_id::<DefaultEnvironment>();
+	set_caller::<DefaultEnvironment>(charlie);
+
+	assert_ok!(xc_regions.mint(region_id(0), charlie));
+	approve_contract(&mut xc_regions, region_id(0));
+	let item = xc_regions.items.get(region_id(0)).unwrap();
+
+	// 1. Frozen items can't be initialized:
+	xc_regions
+		.items
+		.insert(region_id(0), &ItemDetails { is_frozen: true, ..item.clone() });
+	assert_eq!(xc_regions.init(Id::U128(0), Region::default()), Err(XcRegionsError::ItemFrozen));
+	xc_regions.items.insert(region_id(0), &item);
+
+	// 2. Items of a frozen collection can't be initialized:
+	xc_regions.frozen_collections.insert(REGIONS_COLLECTION_ID, &());
+	assert_eq!(
+		xc_regions.init(Id::U128(0), Region::default()),
+		Err(XcRegionsError::CollectionFrozen)
+	);
+	xc_regions.frozen_collections.remove(REGIONS_COLLECTION_ID);
+
+	// 3. The contract must be approved to transfer the item:
+	for approved in [None, Some(bob)] {
+		xc_regions.items.insert(region_id(0), &ItemDetails { approved, ..item.clone() });
+		assert_eq!(
+			xc_regions.init(Id::U128(0), Region::default()),
+			Err(XcRegionsError::MissingApproval)
+		);
+	}
+
+	xc_regions
+		.items
+		.insert(region_id(0), &ItemDetails { approved: Some(contract), ..item });
+	assert_ok!(xc_regions.init(Id::U128(0), Region::default()));
+}
+
+#[ink::test]
+fn cancel_lingering_approval_works() {
+	let DefaultAccounts::<DefaultEnvironment> { bob, charlie, .. } = get_default_accounts();
+	let mut xc_regions = XcRegions::new(REGIONS_COLLECTION_ID, Default::default());
+	set_caller::<DefaultEnvironment>(charlie);
+
+	assert_ok!(xc_regions.mint(region_id(0), charlie));
+	approve_contract(&mut xc_regions, region_id(0));
+	assert_ok!(xc_regions.init(Id::U128(0), Region::default()));
+	assert_ok!(xc_regions.mint(region_id(1), charlie));
+
+	assert_eq!(
+		xc_regions.cancel_lingering_approval(Id::U8(0)),
+		Err(XcRegionsError::InvalidRegionId)
+	);
+
+	// 1. Only the approvals of regions held by the contract can be cancelled:
+	assert_eq!(
+		xc_regions.cancel_lingering_approval(Id::U128(1)),
+		Err(XcRegionsError::RegionNotHeld)
+	);
+	assert_eq!(
+		xc_regions.cancel_lingering_approval(Id::U128(2)),
+		Err(XcRegionsError::RegionNotHeld)
+	);
+
+	// 2. The region must have an approved delegate:
+	let item = xc_regions.items.get(region_id(0)).unwrap();
+	xc_regions
+		.items
+		.insert(region_id(0), &ItemDetails { approved: None, ..item.clone() });
+	assert_eq!(xc_regions.cancel_lingering_approval(Id::U128(0)), Err(XcRegionsError::NoApproval));
+
+	// 3. Cancelling a lingering approval works and is callable by anyone:
+	xc_regions
+		.items
+		.insert(region_id(0), &ItemDetails { approved: Some(bob), ..item });
+	set_caller::<DefaultEnvironment>(bob);
+	assert_ok!(xc_regions.cancel_lingering_approval(Id::U128(0)));
+	assert_eq!(xc_regions._uniques_item(0).unwrap().approved, None);
+}
+
+#[ink::test]
+fn region_transferred_event_works() {
+	let DefaultAccounts::<DefaultEnvironment> { bob, charlie, .. } = get_default_accounts();
+	let mut xc_regions = XcRegions::new(REGIONS_COLLECTION_ID, Default::default());
+	set_caller::<DefaultEnvironment>(charlie);
+
+	let region = Region { begin: 0, end: 10, core: 3, mask: CoreMask::complete() };
+	let raw_region_id = RawRegionId::from(region.id());
+	let metadata = VersionedRegion { version: 0, region: region.clone() };
+
+	// 1. Minting the wrapped region emits the event:
+	assert_ok!(xc_regions.mint((REGIONS_COLLECTION_ID, raw_region_id), charlie));
+	approve_contract(&mut xc_regions, (REGIONS_COLLECTION_ID, raw_region_id));
+	assert_ok!(xc_regions.init(Id::U128(raw_region_id), region.clone()));
+
+	let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+	assert_transferred_event(
+		&emitted_events[emitted_events.len() - 2],
+		raw_region_id,
+		None,
+		Some(charlie),
+		metadata.clone(),
+	);
+
+	// 2. Transferring the wrapped region emits the event:
+	assert_ok!(xc_regions.transfer(bob, Id::U128(raw_region_id), vec![]));
+
+	let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+	assert_transferred_event(
+		&emitted_events.last().unwrap(),
+		raw_region_id,
+		Some(charlie),
+		Some(bob),
+		metadata.clone(),
+	);
+
+	// 3. Burning the wrapped region emits the event:
+	set_caller::<DefaultEnvironment>(bob);
+	assert_ok!(xc_regions.remove(Id::U128(raw_region_id)));
+
+	let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+	assert_transferred_event(
+		&emitted_events[emitted_events.len() - 2],
+		raw_region_id,
+		Some(bob),
+		None,
+		metadata,
+	);
+}
+
+#[ink::test]
+fn metadata_history_works() {
+	let DefaultAccounts::<DefaultEnvironment> { charlie, .. } = get_default_accounts();
+	let mut xc_regions = XcRegions::new(REGIONS_COLLECTION_ID, Default::default());
+	set_caller::<DefaultEnvironment>(charlie);
+
+	let region = Region { begin: 0, end: 10, core: 0, mask: CoreMask::complete() };
+	let raw_region_id = RawRegionId::from(region.id());
+	let id = Id::U128(raw_region_id);
+
+	assert_eq!(xc_regions.metadata_history(Id::U8(0), 0, 10), Err(XcRegionsError::InvalidRegionId));
+	assert_eq!(
+		xc_regions.metadata_history(id.clone(), 0, 10),
+		Ok(MetadataHistoryPage { records: vec![], next: None })
+	);
+
+	// The region gets wrapped, removed, wrapped again with different metadata and partitioned:
+	assert_ok!(xc_regions.mint((REGIONS_COLLECTION_ID, raw_region_id), charlie));
+	approve_contract(&mut xc_regions, (REGIONS_COLLECTION_ID, raw_region_id));
+	assert_ok!(xc_regions.init(id.clone(), region.clone()));
+	advance_block::<DefaultEnvironment>();
+	assert_ok!(xc_regions.remove(id.clone()));
+	approve_contract(&mut xc_regions, (REGIONS_COLLECTION_ID, raw_region_id));
+	assert_ok!(xc_regions.init(id.clone(), Region { end: 20, ..region.clone() }));
+	advance_block::<DefaultEnvironment>();
+	assert_ok!(xc_regions.partition(id.clone(), 5));
+
+	let record = |version, end, block| MetadataRecord {
+		version,
+		region: Region { end, ..region.clone() },
+		initializer: charlie,
+		block,
+	};
+
+	// 1. The whole history is kept and can be paginated:
+	assert_eq!(
+		xc_regions.metadata_history(id.clone(), 0, 2),
+		Ok(MetadataHistoryPage {
+			records: vec![record(0, 10, 0), record(1, 20, 1)],
+			next: Some(2)
+		})
+	);
+	assert_eq!(
+		xc_regions.metadata_history(id.clone(), 2, 2),
+		Ok(MetadataHistoryPage { records: vec![record(2, 5, 2)], next: None })
+	);
+	assert_eq!(
+		xc_regions.metadata_history(id.clone(), 5, 2),
+		Ok(MetadataHistoryPage { records: vec![], next: None })
+	);
+
+	// 2. A zero limit returns a full page instead of an empty page pointing to itself:
+	assert_eq!(
+		xc_regions.metadata_history(id, 1, 0),
+		Ok(MetadataHistoryPage { records: vec![record(1, 20, 1), record(2, 5, 2)], next: None })
+	);
+}
+
+#[ink::test]
+fn get_attribute_works() {
+	let DefaultAccounts::<DefaultEnvironment> { charlie, .. } = get_default_accounts();
+	let mut xc_regions = XcRegions::new(REGIONS_COLLECTION_ID, Default::default());
+	set_caller::<DefaultEnvironment>(charlie);
+
+	let attribute = |id, key: &str| xc_regions.get_attribute(id, String::from(key));
+	let collection_id = Id::U32(REGIONS_COLLECTION_ID);
+
+	// 1. The collection has a name and a symbol:
+	assert_eq!(attribute(collection_id.clone(), "name"), Some(String::from("Cross-Chain Regions")));
+	assert_eq!(attribute(collection_id, "symbol"), Some(String::from("xcREGION")));
+
+	// 2. Regions without metadata don't have attributes:
+	let region = Region { begin: 2, end: 10, core: 3, mask: CoreMask::from_chunk(0, 40) };
+	let raw_region_id = RawRegionId::from(region.id());
+	assert_eq!(attribute(Id::U128(raw_region_id), "begin"), None);
+
+	// 3. The attributes are computed from the metadata of the region:
+	assert_ok!(xc_regions.mint((REGIONS_COLLECTION_ID, raw_region_id), charlie));
+	approve_contract(&mut xc_regions, (REGIONS_COLLECTION_ID, raw_region_id));
+	assert_ok!(xc_regions.init(Id::U128(raw_region_id), region));
+
+	let attribute =
+		|key: &str| xc_regions.get_attribute(Id::U128(raw_region_id), String::from(key));
+	assert_eq!(attribute("begin"), Some(String::from("2")));
+	assert_eq!(attribute("end"), Some(String::from("10")));
+	assert_eq!(attribute("core"), Some(String::from("3")));
+	assert_eq!(attribute("mask"), Some(String::from("0xffffffffff0000000000")));
+	assert_eq!(attribute("length"), Some(String::from("8")));
+	assert_eq!(attribute("occupancy"), Some(String::from("50.00%")));
+	assert_eq!(attribute("version"), Some(String::from("0")));
+	assert_eq!(attribute("owner"), None);
+}
+
+#[ink::test]
+fn token_uri_works() {
+	let DefaultAccounts::<DefaultEnvironment> { alice, charlie, .. } = get_default_accounts();
+	set_caller::<DefaultEnvironment>(alice);
+	let mut xc_regions = XcRegions::new(REGIONS_COLLECTION_ID, Default::default());
+	assert_ok!(xc_regions.set_timeslice_period(1));
+
+	let mask = CoreMask::from_chunk(0, 40) | CoreMask::from_chunk(60, 80);
+	let region = Region { begin: 0, end: 8, core: 2, mask };
+	let raw_region_id = RawRegionId::from(region.id());
+
+	// 1. Regions without metadata can't be rendered:
+	assert_eq!(
+		xc_regions.token_uri(Id::U128(raw_region_id)),
+		Err(XcRegionsError::MetadataNotFound)
+	);
+
+	set_caller::<DefaultEnvironment>(charlie);
+	assert_ok!(xc_regions.mint((REGIONS_COLLECTION_ID, raw_region_id), charlie));
+	approve_contract(&mut xc_regions, (REGIONS_COLLECTION_ID, raw_region_id));
+	assert_ok!(xc_regions.init(Id::U128(raw_region_id), region.clone()));
+	(0..2).for_each(|_| advance_block::<DefaultEnvironment>());
+
+	// 2. The SVG is returned as a data URI:
+	let uri = xc_regions.token_uri(Id::U128(raw_region_id)).unwrap();
+	assert!(uri.starts_with(SVG_DATA_URI_PREFIX));
+	assert!(uri[SVG_DATA_URI_PREFIX.len()..].starts_with("%3Csvg%20xmlns="));
+	assert!(!uri.contains(|c| matches!(c, '<' | '>' | '#' | ' ' | '"')));
+
+	// 3. The image shows the core, the mask and the timeline of the region:
+	let versioned = VersionedRegion { version: 0, region };
+	let svg = render_svg(&versioned, 2);
+	assert!(svg.contains("Core 2 - v0</text>"));
+	assert!(svg.contains(">Active</text>"));
+	// Each run of set bits is a single rectangle:
+	assert!(svg.contains("<rect x='40' y='60' width='200' height='30' fill='#2ecc71'/>"));
+	assert!(svg.contains("<rect x='340' y='60' width='100' height='30' fill='#2ecc71'/>"));
+	assert_eq!(svg.matches("fill='#2ecc71'/>").count(), 3);
+	// The marker is placed at the current timeslice:
+	assert!(svg.contains("<circle cx='140' cy='140' r='6' fill='#2ecc71'/>"));
+
+	// 4. The marker is clamped to the timeline:
+	let svg = render_svg(&versioned, 10);
+	assert!(svg.contains(">Expired</text>"));
+	assert!(svg.contains("<circle cx='440' cy='140' r='6' fill='#7f8c8d'/>"));
+
+	// 5. The rendering is deterministic:
+	assert_eq!(render_svg(&versioned, 10), svg);
+}
+
+#[ink::test]
+fn metadata_version_gets_updated() {
+	let DefaultAccounts::<DefaultEnvironment> { charlie, .. } = get_default_accounts();
+	let mut xc_regions = XcRegions::new(REGIONS_COLLECTION_ID, Default::default());
+	set_caller::<DefaultEnvironment>(charlie);
+
+	assert_ok!(xc_regions.mint(region_id(0), charlie));
+	approve_contract(&mut xc_regions, region_id(0));
+
+	assert_ok!(xc_regions.init(Id::U128(0), Region::default()));
+	assert_eq!(
+		xc_regions.get_metadata(Id::U128(0)),
+		Ok(VersionedRegion { version: 0, region: Region::default() })
+	);
+
+	assert_ok!(xc_regions.remove(Id::U128(0)));
+
+	// Removing the region clears the approval, so it has to be given again.
+	approve_contract(&mut xc_regions, region_id(0));
+	assert_ok!(xc_regions.init(Id::U128(0), Region::default()));
+	assert_eq!(
+		xc_regions.get_metadata(Id::U128(0)),
+		Ok(VersionedRegion { version: 1, region: Region::default() })
+	);
+}
+
+// Helper functions for test
+fn assert_init_event(
+	event: &ink::env::test::EmittedEvent,
+	expected_region_id: RawRegionId,
+	expected_metadata: Region,
+	expected_version: Version,
+) {
+	let decoded_event = <Event as scale::Decode>::decode(&mut &event.data[..])
+		.expect("encountered invalid contract event data buffer");
+	if let Event::RegionInitialized(RegionInitialized { region_id, metadata, version }) =
+		decoded_event
+	{
+		assert_eq!(
 			region_id, expected_region_id,
 			"encountered invalid RegionInitialized.region_id"
 		);
@@ -262,10 +2204,360 @@ fn assert_removed_event(event: &ink::env::test::EmittedEvent, expected_region_id
 	}
 }
 
+fn assert_challenged_event(
+	event: &ink::env::test::EmittedEvent,
+	expected_region_id: RawRegionId,
+	expected_challenger: AccountId,
+	expected_metadata: Region,
+	expected_version: Version,
+) {
+	let decoded_event = <Event as scale::Decode>::decode(&mut &event.data[..])
+		.expect("encountered invalid contract event data buffer");
+	if let Event::RegionChallenged(RegionChallenged { region_id, challenger, metadata, version }) =
+		decoded_event
+	{
+		assert_eq!(region_id, expected_region_id, "encountered invalid RegionChallenged.region_id");
+		assert_eq!(
+			challenger, expected_challenger,
+			"encountered invalid RegionChallenged.challenger"
+		);
+		assert_eq!(metadata, expected_metadata, "encountered invalid RegionChallenged.metadata");
+		assert_eq!(version, expected_version, "encountered invalid RegionChallenged.version");
+	} else {
+		panic!("encountered unexpected event kind: expected a RegionChallenged event")
+	}
+}
+
+fn assert_finalized_event(event: &ink::env::test::EmittedEvent, expected_region_id: RawRegionId) {
+	let decoded_event = <Event as scale::Decode>::decode(&mut &event.data[..])
+		.expect("encountered invalid contract event data buffer");
+	if let Event::RegionFinalized(RegionFinalized { region_id }) = decoded_event {
+		assert_eq!(region_id, expected_region_id, "encountered invalid RegionFinalized.region_id");
+	} else {
+		panic!("encountered unexpected event kind: expected a RegionFinalized event")
+	}
+}
+
+fn assert_attested_event(
+	event: &ink::env::test::EmittedEvent,
+	expected_region_id: RawRegionId,
+	expected_attestors: Vec<RelayerKey>,
+	expected_nonce: AttestationNonce,
+) {
+	let decoded_event = <Event as scale::Decode>::decode(&mut &event.data[..])
+		.expect("encountered invalid contract event data buffer");
+	if let Event::RegionAttested(RegionAttested { region_id, attestors, nonce }) = decoded_event {
+		assert_eq!(region_id, expected_region_id, "encountered invalid RegionAttested.region_id");
+		assert_eq!(attestors, expected_attestors, "encountered invalid RegionAttested.attestors");
+		assert_eq!(nonce, expected_nonce, "encountered invalid RegionAttested.nonce");
+	} else {
+		panic!("encountered unexpected event kind: expected a RegionAttested event")
+	}
+}
+
+fn assert_partitioned_event(
+	event: &ink::env::test::EmittedEvent,
+	expected_region_id: RawRegionId,
+	expected_pivot: Timeslice,
+	expected_new_regions: ((RawRegionId, Version), (RawRegionId, Version)),
+) {
+	let decoded_event = <Event as scale::Decode>::decode(&mut &event.data[..])
+		.expect("encountered invalid contract event data buffer");
+	if let Event::RegionPartitioned(RegionPartitioned { region_id, pivot, new_regions }) =
+		decoded_event
+	{
+		assert_eq!(
+			region_id, expected_region_id,
+			"encountered invalid RegionPartitioned.region_id"
+		);
+		assert_eq!(pivot, expected_pivot, "encountered invalid RegionPartitioned.pivot");
+		assert_eq!(
+			new_regions, expected_new_regions,
+			"encountered invalid RegionPartitioned.new_regions"
+		);
+	} else {
+		panic!("encountered unexpected event kind: expected a RegionPartitioned event")
+	}
+}
+
+fn assert_interlaced_event(
+	event: &ink::env::test::EmittedEvent,
+	expected_region_id: RawRegionId,
+	expected_pivot_mask: CoreMask,
+	expected_new_regions: ((RawRegionId, Version), (RawRegionId, Version)),
+) {
+	let decoded_event = <Event as scale::Decode>::decode(&mut &event.data[..])
+		.expect("encountered invalid contract event data buffer");
+	if let Event::RegionInterlaced(RegionInterlaced { region_id, pivot_mask, new_regions }) =
+		decoded_event
+	{
+		assert_eq!(region_id, expected_region_id, "encountered invalid RegionInterlaced.region_id");
+		assert_eq!(
+			pivot_mask, expected_pivot_mask,
+			"encountered invalid RegionInterlaced.pivot_mask"
+		);
+		assert_eq!(
+			new_regions, expected_new_regions,
+			"encountered invalid RegionInterlaced.new_regions"
+		);
+	} else {
+		panic!("encountered unexpected event kind: expected a RegionInterlaced event")
+	}
+}
+
+fn assert_merged_event(
+	event: &ink::env::test::EmittedEvent,
+	expected_region_ids: (RawRegionId, RawRegionId),
+	expected_new_region_id: RawRegionId,
+	expected_version: Version,
+) {
+	let decoded_event = <Event as scale::Decode>::decode(&mut &event.data[..])
+		.expect("encountered invalid contract event data buffer");
+	if let Event::RegionsMerged(RegionsMerged { region_ids, new_region_id, version }) =
+		decoded_event
+	{
+		assert_eq!(region_ids, expected_region_ids, "encountered invalid RegionsMerged.region_ids");
+		assert_eq!(
+			new_region_id, expected_new_region_id,
+			"encountered invalid RegionsMerged.new_region_id"
+		);
+		assert_eq!(version, expected_version, "encountered invalid RegionsMerged.version");
+	} else {
+		panic!("encountered unexpected event kind: expected a RegionsMerged event")
+	}
+}
+
+fn assert_teleported_event(
+	event: &ink::env::test::EmittedEvent,
+	expected_region_id: RawRegionId,
+	expected_owner: AccountId,
+	expected_dest: VersionedMultiLocation,
+) {
+	let decoded_event = <Event as scale::Decode>::decode(&mut &event.data[..])
+		.expect("encountered invalid contract event data buffer");
+	if let Event::RegionTeleported(RegionTeleported { region_id, owner, dest }) = decoded_event {
+		assert_eq!(region_id, expected_region_id, "encountered invalid RegionTeleported.region_id");
+		assert_eq!(owner, expected_owner, "encountered invalid RegionTeleported.owner");
+		assert_eq!(dest, expected_dest, "encountered invalid RegionTeleported.dest");
+	} else {
+		panic!("encountered unexpected event kind: expected a RegionTeleported event")
+	}
+}
+
+fn assert_assigned_event(
+	event: &ink::env::test::EmittedEvent,
+	expected_region_id: RawRegionId,
+	expected_task: ParaId,
+	expected_finality: Finality,
+) {
+	let decoded_event = <Event as scale::Decode>::decode(&mut &event.data[..])
+		.expect("encountered invalid contract event data buffer");
+	if let Event::RegionAssigned(RegionAssigned { region_id, task, finality }) = decoded_event {
+		assert_eq!(region_id, expected_region_id, "encountered invalid RegionAssigned.region_id");
+		assert_eq!(task, expected_task, "encountered invalid RegionAssigned.task");
+		assert_eq!(finality, expected_finality, "encountered invalid RegionAssigned.finality");
+	} else {
+		panic!("encountered unexpected event kind: expected a RegionAssigned event")
+	}
+}
+
+fn assert_assignment_requested_event(
+	event: &ink::env::test::EmittedEvent,
+	expected_region_id: RawRegionId,
+	expected_task: ParaId,
+	expected_finality: Finality,
+) {
+	let decoded_event = <Event as scale::Decode>::decode(&mut &event.data[..])
+		.expect("encountered invalid contract event data buffer");
+	if let Event::AssignmentRequested(AssignmentRequested { region_id, task, finality }) =
+		decoded_event
+	{
+		assert_eq!(
+			region_id, expected_region_id,
+			"encountered invalid AssignmentRequested.region_id"
+		);
+		assert_eq!(task, expected_task, "encountered invalid AssignmentRequested.task");
+		assert_eq!(finality, expected_finality, "encountered invalid AssignmentRequested.finality");
+	} else {
+		panic!("encountered unexpected event kind: expected an AssignmentRequested event")
+	}
+}
+
+fn assert_retrieval_requested_event(
+	event: &ink::env::test::EmittedEvent,
+	expected_region_id: RawRegionId,
+) {
+	let decoded_event = <Event as scale::Decode>::decode(&mut &event.data[..])
+		.expect("encountered invalid contract event data buffer");
+	if let Event::RetrievalRequested(RetrievalRequested { region_id }) = decoded_event {
+		assert_eq!(
+			region_id, expected_region_id,
+			"encountered invalid RetrievalRequested.region_id"
+		);
+	} else {
+		panic!("encountered unexpected event kind: expected a RetrievalRequested event")
+	}
+}
+
+fn assert_pooled_event(
+	event: &ink::env::test::EmittedEvent,
+	expected_region_id: RawRegionId,
+	expected_payee: AccountId,
+) {
+	let decoded_event = <Event as scale::Decode>::decode(&mut &event.data[..])
+		.expect("encountered invalid contract event data buffer");
+	if let Event::RegionPooled(RegionPooled { region_id, payee }) = decoded_event {
+		assert_eq!(region_id, expected_region_id, "encountered invalid RegionPooled.region_id");
+		assert_eq!(payee, expected_payee, "encountered invalid RegionPooled.payee");
+	} else {
+		panic!("encountered unexpected event kind: expected a RegionPooled event")
+	}
+}
+
+fn assert_revenue_claim_requested_event(
+	event: &ink::env::test::EmittedEvent,
+	expected_region_id: RawRegionId,
+	expected_timeslices: (Timeslice, Timeslice),
+) {
+	let decoded_event = <Event as scale::Decode>::decode(&mut &event.data[..])
+		.expect("encountered invalid contract event data buffer");
+	if let Event::RevenueClaimRequested(RevenueClaimRequested { region_id, timeslices }) =
+		decoded_event
+	{
+		assert_eq!(
+			region_id, expected_region_id,
+			"encountered invalid RevenueClaimRequested.region_id"
+		);
+		assert_eq!(
+			timeslices, expected_timeslices,
+			"encountered invalid RevenueClaimRequested.timeslices"
+		);
+	} else {
+		panic!("encountered unexpected event kind: expected a RevenueClaimRequested event")
+	}
+}
+
+fn assert_revenue_claimed_event(
+	event: &ink::env::test::EmittedEvent,
+	expected_region_id: RawRegionId,
+	expected_timeslices: (Timeslice, Timeslice),
+) {
+	let decoded_event = <Event as scale::Decode>::decode(&mut &event.data[..])
+		.expect("encountered invalid contract event data buffer");
+	if let Event::RevenueClaimed(RevenueClaimed { region_id, timeslices }) = decoded_event {
+		assert_eq!(region_id, expected_region_id, "encountered invalid RevenueClaimed.region_id");
+		assert_eq!(
+			timeslices, expected_timeslices,
+			"encountered invalid RevenueClaimed.timeslices"
+		);
+	} else {
+		panic!("encountered unexpected event kind: expected a RevenueClaimed event")
+	}
+}
+
+fn assert_reaped_event(
+	event: &ink::env::test::EmittedEvent,
+	expected_region_id: RawRegionId,
+	expected_reaper: AccountId,
+) {
+	let decoded_event = <Event as scale::Decode>::decode(&mut &event.data[..])
+		.expect("encountered invalid contract event data buffer");
+	if let Event::RegionReaped(RegionReaped { region_id, reaper }) = decoded_event {
+		assert_eq!(region_id, expected_region_id, "encountered invalid RegionReaped.region_id");
+		assert_eq!(reaper, expected_reaper, "encountered invalid RegionReaped.reaper");
+	} else {
+		panic!("encountered unexpected event kind: expected a RegionReaped event")
+	}
+}
+
+fn assert_transferred_event(
+	event: &ink::env::test::EmittedEvent,
+	expected_region_id: RawRegionId,
+	expected_from: Option<AccountId>,
+	expected_to: Option<AccountId>,
+	expected_metadata: VersionedRegion,
+) {
+	let decoded_event = <Event as scale::Decode>::decode(&mut &event.data[..])
+		.expect("encountered invalid contract event data buffer");
+	if let Event::RegionTransferred(RegionTransferred { region_id, from, to, core, metadata }) =
+		decoded_event
+	{
+		assert_eq!(
+			region_id, expected_region_id,
+			"encountered invalid RegionTransferred.region_id"
+		);
+		assert_eq!(from, expected_from, "encountered invalid RegionTransferred.from");
+		assert_eq!(to, expected_to, "encountered invalid RegionTransferred.to");
+		assert_eq!(
+			core, expected_metadata.region.core,
+			"encountered invalid RegionTransferred.core"
+		);
+		assert_eq!(metadata, expected_metadata, "encountered invalid RegionTransferred.metadata");
+	} else {
+		panic!("encountered unexpected event kind: expected a RegionTransferred event")
+	}
+}
+
+fn relayer_keypair(seed: u8) -> Keypair {
+	MiniSecretKey::from_bytes(&[seed; 32])
+		.expect("valid secret key")
+		.expand_to_keypair(ExpansionMode::Ed25519)
+}
+
+fn relayer_key(keypair: &Keypair) -> RelayerKey {
+	RelayerKey::Sr25519(keypair.public.to_bytes())
+}
+
+fn ed25519_relayer_key(signing_key: &SigningKey) -> RelayerKey {
+	RelayerKey::Ed25519(signing_key.verifying_key().to_bytes())
+}
+
+/// The message relayers sign to attest the metadata of a region.
+fn attestation_message(
+	raw_region_id: RawRegionId,
+	region: &Region,
+	nonce: AttestationNonce,
+	valid_until: BlockNumber,
+) -> Vec<u8> {
+	let contract = ink::env::account_id::<DefaultEnvironment>();
+	scale::Encode::encode(&(contract, raw_region_id, region, nonce, valid_until))
+}
+
+/// Signs the metadata of a region the same way a relayer would.
+fn attest(
+	keypair: &Keypair,
+	raw_region_id: RawRegionId,
+	region: &Region,
+	nonce: AttestationNonce,
+	valid_until: BlockNumber,
+) -> (RelayerKey, Attestation) {
+	let message = attestation_message(raw_region_id, region, nonce, valid_until);
+
+	(relayer_key(keypair), keypair.sign_simple(b"substrate", &message).to_bytes())
+}
+
+/// Signs the metadata of a region the same way a relayer with an ed25519 key would.
+fn attest_ed25519(
+	signing_key: &SigningKey,
+	raw_region_id: RawRegionId,
+	region: &Region,
+	nonce: AttestationNonce,
+	valid_until: BlockNumber,
+) -> (RelayerKey, Attestation) {
+	let message = attestation_message(raw_region_id, region, nonce, valid_until);
+
+	(ed25519_relayer_key(signing_key), signing_key.sign(&message).to_bytes())
+}
+
 pub fn region_id(region_id: RawRegionId) -> (CollectionId, RawRegionId) {
 	(REGIONS_COLLECTION_ID, region_id)
 }
 
+/// Approves the contract to transfer the item, as required for initializing the region.
+pub fn approve_contract(xc_regions: &mut XcRegions, id: (CollectionId, RawRegionId)) {
+	assert_ok!(xc_regions.approve_transfer(id, ink::env::account_id::<DefaultEnvironment>()));
+}
+
 pub fn get_default_accounts() -> DefaultAccounts<DefaultEnvironment> {
 	default_accounts::<DefaultEnvironment>()
 }
@@ -33,3 +33,19 @@ pub trait RegionMetadata {
 	#[ink(message)]
 	fn remove(&mut self, id: Id) -> Result<(), XcRegionsError>;
 }
+
+#[openbrush::wrapper]
+pub type RegionStateRef = dyn RegionState;
+
+/// The state of a wrapped region which other contracts, such as the market, need to know for
+/// deciding whether the region can change hands.
+#[openbrush::trait_definition]
+pub trait RegionState {
+	/// Returns whether the metadata of the region can still be challenged.
+	#[ink(message)]
+	fn is_pending(&self, id: Id) -> bool;
+
+	/// Returns whether the region is finally assigned to a task.
+	#[ink(message)]
+	fn is_finally_assigned(&self, id: Id) -> bool;
+}
@@ -13,8 +13,58 @@
 // You should have received a copy of the GNU General Public License
 // along with RegionX.  If not, see <https://www.gnu.org/licenses/>.
 
-use openbrush::contracts::psp34::PSP34Error;
-use primitives::{coretime::Region, Version};
+use ink::prelude::{format, string::String, vec::Vec};
+use openbrush::{
+	contracts::{
+		ownable::OwnableError,
+		psp34::{Id, PSP34Error},
+	},
+	traits::{AccountId, BlockNumber},
+};
+use primitives::{
+	assets::{AssetId, AssetsCallIndices},
+	broker::BrokerCallIndices,
+	coretime::{CoreIndex, Finality, ParaId, Region, RegionError, Timeslice, CORE_MASK_BIT_LEN},
+	nfts::NftsCallIndices,
+	pallet_xcm::XcmCallIndices,
+	proof::ProofError,
+	uniques::UniquesCallIndices,
+	Balance, Version,
+};
+
+/// The public key of a relayer, along with the signature scheme it belongs to.
+#[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+pub enum RelayerKey {
+	/// An sr25519 public key. The relayer signs in the `substrate` signing context, just like
+	/// Substrate accounts do.
+	Sr25519([u8; 32]),
+	/// An ed25519 public key.
+	Ed25519([u8; 32]),
+}
+
+/// A signature of a relayer, made with the scheme of its key.
+pub type Attestation = [u8; 64];
+
+/// The nonce included in the attestations of a region, used for replay protection.
+pub type AttestationNonce = u64;
+
+/// The maximum number of regions which can be initialized or removed in a single batch.
+pub const MAX_BATCH_SIZE: usize = 20;
+
+/// The name of the collection of wrapped regions, exposed through the `name` attribute.
+pub const COLLECTION_NAME: &str = "Cross-Chain Regions";
+
+/// The symbol of the collection of wrapped regions, exposed through the `symbol` attribute.
+pub const COLLECTION_SYMBOL: &str = "xcREGION";
+
+/// The maximum number of items returned in a single page by `regions_of` and
+/// `metadata_history`.
+pub const MAX_PAGE_SIZE: u32 = 50;
+
+/// The default number of Coretime chain blocks by which the state root a storage proof is verified
+/// against may lag behind the latest known state root.
+pub const MAX_STATE_ROOT_AGE: BlockNumber = 10;
 
 #[derive(scale::Decode, scale::Encode, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
@@ -37,6 +87,123 @@ pub enum XcRegionsError {
 	RuntimeError,
 	/// An psp34 error occured.
 	Psp34(PSP34Error),
+	/// The state root of the Coretime chain at the specified block is not known.
+	StateRootNotFound,
+	/// The storage proof of the region record couldn't be verified.
+	InvalidProof(ProofError),
+	/// The optimistic initialization of regions is not enabled.
+	OptimisticInitDisabled,
+	/// The caller didn't send the bond required by the optimistic initialization.
+	MissingBond,
+	/// The region is not pending, i.e. it wasn't optimistically initialized or it is already
+	/// finalized.
+	RegionNotPending,
+	/// The challenge period of the region is over.
+	ChallengePeriodOver,
+	/// The challenge period of the region is not over yet.
+	ChallengePeriodNotOver,
+	/// The caller is not the arbiter of the optimistic initialization.
+	NotArbiter,
+	/// The evidence of the challenge shows that the metadata is correct.
+	MetadataNotFraudulent,
+	/// Failed to transfer the bond.
+	TransferFailed,
+	/// An ownable error occured.
+	Ownable(OwnableError),
+	/// The initialization of regions through relayer attestations is not enabled.
+	AttestationDisabled,
+	/// The nonce of the attestations doesn't match the current attestation nonce of the region.
+	InvalidNonce,
+	/// One of the attestations isn't a valid signature of a registered relayer, or a relayer
+	/// attested more than once.
+	InvalidAttestation,
+	/// The number of attestations is below the attestation threshold.
+	NotEnoughAttestations,
+	/// The relayer is already registered.
+	RelayerAlreadyRegistered,
+	/// The relayer is not registered.
+	RelayerNotRegistered,
+	/// The attestation threshold can't exceed the number of registered relayers.
+	InvalidThreshold,
+	/// The caller is not the owner of the wrapped region.
+	NotOwner,
+	/// The operation is not allowed while the metadata of the region can still be challenged.
+	RegionPending,
+	/// The region was derived from another region within the contract. The underlying region
+	/// can't be returned until all the regions derived from it are merged back, or until all of
+	/// them expired and got reaped.
+	DerivedRegion,
+	/// The region can't be split or merged the requested way.
+	Region(RegionError),
+	/// The regions to be merged weren't derived from the same underlying region.
+	DifferentUnderlying,
+	/// Sending regions back to the Coretime chain is not enabled.
+	TeleportDisabled,
+	/// The destination isn't a valid location of the Coretime chain.
+	InvalidDestination,
+	/// Dispatching calls on the Coretime chain is not enabled.
+	TransactDisabled,
+	/// The region is assigned to a task, so the operation isn't allowed.
+	RegionAssigned,
+	/// The region is contributed to the instantaneous coretime pool, so the operation isn't
+	/// allowed.
+	RegionPooled,
+	/// The region isn't contributed to the instantaneous coretime pool, or all its revenue has
+	/// been claimed.
+	RegionNotPooled,
+	/// The batch contains more than `MAX_BATCH_SIZE` items.
+	BatchTooLarge,
+	/// Some items of the batch failed. Contains the index of each failed item along with its
+	/// error. None of the items of the batch are applied.
+	BatchFailed(Vec<(u32, XcRegionsError)>),
+	/// Reaping expired regions is not enabled.
+	ReapDisabled,
+	/// The region hasn't expired yet.
+	RegionNotExpired,
+	/// The underlying region is frozen, so it can't be transferred to the contract.
+	ItemFrozen,
+	/// The collection of the underlying region is frozen, so the region can't be transferred to
+	/// the contract.
+	CollectionFrozen,
+	/// The contract isn't approved to transfer the underlying region.
+	MissingApproval,
+	/// The underlying region isn't held by the contract.
+	RegionNotHeld,
+	/// The underlying region doesn't have an approved delegate.
+	NoApproval,
+	/// The state root is older than the latest known state root by more than
+	/// `max_state_root_age` blocks.
+	StateRootTooOld,
+	/// The attestations are no longer valid.
+	AttestationExpired,
+	/// The caller didn't approve the contract to spend the relay chain tokens paying for the
+	/// execution on the Coretime chain.
+	MissingFee,
+	/// The underlying region was moved to the Coretime chain for dispatching calls on it, so it
+	/// can't be returned on this chain until it is retrieved.
+	RegionOnCoretime,
+	/// The region has no assignment awaiting confirmation.
+	AssignmentNotFound,
+	/// None of the remaining revenue of the pooled region can be claimed yet.
+	NoRevenueToClaim,
+	/// The region is owned by a contract, so it can't be reaped.
+	OwnedByContract,
+	/// The underlying region isn't with the account of the contract on the Coretime chain.
+	RegionNotOnCoretime,
+	/// The region was assigned too recently to be reassigned.
+	ReassignmentTooSoon,
+	/// The previous revenue claim of the pooled region awaits confirmation.
+	ClaimPending,
+	/// The pooled region has no revenue claim awaiting confirmation.
+	ClaimNotFound,
+	/// The reported outcome of the revenue claim is outside of the claimed timeslices.
+	InvalidClaim,
+}
+
+impl From<OwnableError> for XcRegionsError {
+	fn from(error: OwnableError) -> Self {
+		XcRegionsError::Ownable(error)
+	}
 }
 
 impl core::fmt::Display for XcRegionsError {
@@ -50,13 +217,358 @@ impl core::fmt::Display for XcRegionsError {
 			XcRegionsError::VersionNotFound => write!(f, "VersionNotFound"),
 			XcRegionsError::RuntimeError => write!(f, "RuntimeError"),
 			XcRegionsError::Psp34(err) => write!(f, "{:?}", err),
+			XcRegionsError::StateRootNotFound => write!(f, "StateRootNotFound"),
+			XcRegionsError::InvalidProof(err) => write!(f, "{:?}", err),
+			XcRegionsError::OptimisticInitDisabled => write!(f, "OptimisticInitDisabled"),
+			XcRegionsError::MissingBond => write!(f, "MissingBond"),
+			XcRegionsError::RegionNotPending => write!(f, "RegionNotPending"),
+			XcRegionsError::ChallengePeriodOver => write!(f, "ChallengePeriodOver"),
+			XcRegionsError::ChallengePeriodNotOver => write!(f, "ChallengePeriodNotOver"),
+			XcRegionsError::NotArbiter => write!(f, "NotArbiter"),
+			XcRegionsError::MetadataNotFraudulent => write!(f, "MetadataNotFraudulent"),
+			XcRegionsError::TransferFailed => write!(f, "TransferFailed"),
+			XcRegionsError::Ownable(err) => write!(f, "{:?}", err),
+			XcRegionsError::AttestationDisabled => write!(f, "AttestationDisabled"),
+			XcRegionsError::InvalidNonce => write!(f, "InvalidNonce"),
+			XcRegionsError::InvalidAttestation => write!(f, "InvalidAttestation"),
+			XcRegionsError::NotEnoughAttestations => write!(f, "NotEnoughAttestations"),
+			XcRegionsError::RelayerAlreadyRegistered => write!(f, "RelayerAlreadyRegistered"),
+			XcRegionsError::RelayerNotRegistered => write!(f, "RelayerNotRegistered"),
+			XcRegionsError::InvalidThreshold => write!(f, "InvalidThreshold"),
+			XcRegionsError::NotOwner => write!(f, "NotOwner"),
+			XcRegionsError::RegionPending => write!(f, "RegionPending"),
+			XcRegionsError::DerivedRegion => write!(f, "DerivedRegion"),
+			XcRegionsError::Region(err) => write!(f, "{:?}", err),
+			XcRegionsError::DifferentUnderlying => write!(f, "DifferentUnderlying"),
+			XcRegionsError::TeleportDisabled => write!(f, "TeleportDisabled"),
+			XcRegionsError::InvalidDestination => write!(f, "InvalidDestination"),
+			XcRegionsError::TransactDisabled => write!(f, "TransactDisabled"),
+			XcRegionsError::RegionAssigned => write!(f, "RegionAssigned"),
+			XcRegionsError::RegionPooled => write!(f, "RegionPooled"),
+			XcRegionsError::RegionNotPooled => write!(f, "RegionNotPooled"),
+			XcRegionsError::BatchTooLarge => write!(f, "BatchTooLarge"),
+			XcRegionsError::BatchFailed(errors) => write!(f, "{:?}", errors),
+			XcRegionsError::ReapDisabled => write!(f, "ReapDisabled"),
+			XcRegionsError::RegionNotExpired => write!(f, "RegionNotExpired"),
+			XcRegionsError::ItemFrozen => write!(f, "ItemFrozen"),
+			XcRegionsError::CollectionFrozen => write!(f, "CollectionFrozen"),
+			XcRegionsError::MissingApproval => write!(f, "MissingApproval"),
+			XcRegionsError::RegionNotHeld => write!(f, "RegionNotHeld"),
+			XcRegionsError::NoApproval => write!(f, "NoApproval"),
+			XcRegionsError::StateRootTooOld => write!(f, "StateRootTooOld"),
+			XcRegionsError::AttestationExpired => write!(f, "AttestationExpired"),
+			XcRegionsError::MissingFee => write!(f, "MissingFee"),
+			XcRegionsError::RegionOnCoretime => write!(f, "RegionOnCoretime"),
+			XcRegionsError::AssignmentNotFound => write!(f, "AssignmentNotFound"),
+			XcRegionsError::NoRevenueToClaim => write!(f, "NoRevenueToClaim"),
+			XcRegionsError::OwnedByContract => write!(f, "OwnedByContract"),
+			XcRegionsError::RegionNotOnCoretime => write!(f, "RegionNotOnCoretime"),
+			XcRegionsError::ReassignmentTooSoon => write!(f, "ReassignmentTooSoon"),
+			XcRegionsError::ClaimPending => write!(f, "ClaimPending"),
+			XcRegionsError::ClaimNotFound => write!(f, "ClaimNotFound"),
+			XcRegionsError::InvalidClaim => write!(f, "InvalidClaim"),
 		}
 	}
 }
 
 #[derive(scale::Decode, scale::Encode, Default, Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VersionedRegion {
 	pub version: Version,
 	pub region: Region,
 }
+
+impl VersionedRegion {
+	/// Returns the value of the PSP34 metadata attribute `key`, rendered as a string.
+	///
+	/// The supported keys are `begin`, `end`, `core`, `mask`, `length`, `occupancy` and
+	/// `version`. The mask is rendered as a hex string and the occupancy as a percentage.
+	pub fn attribute(&self, key: &str) -> Option<String> {
+		let region = &self.region;
+		let value = match key {
+			"begin" => format!("{}", region.begin),
+			"end" => format!("{}", region.end),
+			"core" => format!("{}", region.core),
+			"mask" => format!("{:#022x}", u128::from(region.mask)),
+			"length" => format!("{}", region.end.saturating_sub(region.begin)),
+			"occupancy" => {
+				// In hundredths of a percent.
+				let occupancy = region.mask.count_ones() * 10_000 / CORE_MASK_BIT_LEN as u32;
+				format!("{}.{:02}%", occupancy / 100, occupancy % 100)
+			},
+			"version" => format!("{}", self.version),
+			_ => return None,
+		};
+
+		Some(value)
+	}
+}
+
+/// The pallet holding the underlying regions on the chain on which the contract is deployed.
+#[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Backend {
+	/// The regions are held in pallet-uniques. The indices are used for encoding its calls.
+	Uniques(UniquesCallIndices),
+	/// The regions are held in pallet-nfts. The indices are used for encoding its calls.
+	Nfts(NftsCallIndices),
+}
+
+impl Default for Backend {
+	fn default() -> Self {
+		Backend::Uniques(Default::default())
+	}
+}
+
+/// The configuration of the optimistic initialization of regions.
+#[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OptimisticConfig {
+	/// The bond the initializer has to post. It is returned once the region gets finalized.
+	#[cfg_attr(feature = "serde", serde(with = "primitives::serde_utils::u128_string"))]
+	pub bond: Balance,
+	/// The number of blocks during which the metadata of a region can be challenged.
+	pub challenge_period: BlockNumber,
+	/// The account whose verdict is accepted as evidence when challenging the metadata of a
+	/// region. If not set, the metadata can only be challenged with a storage proof.
+	#[cfg_attr(feature = "serde", serde(with = "primitives::serde_utils::option_account_id"))]
+	pub arbiter: Option<AccountId>,
+}
+
+/// A region which was optimistically initialized and whose metadata can still be challenged.
+#[derive(scale::Decode, scale::Encode, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PendingRegion {
+	/// The account which initialized the region and posted the bond.
+	#[cfg_attr(feature = "serde", serde(with = "primitives::serde_utils::account_id"))]
+	pub initializer: AccountId,
+	/// The posted bond.
+	#[cfg_attr(feature = "serde", serde(with = "primitives::serde_utils::u128_string"))]
+	pub bond: Balance,
+	/// The last block at which the metadata of the region can be challenged.
+	pub challenge_deadline: BlockNumber,
+}
+
+/// The relay chain token in which the fees of the XCM sent by the contract are paid.
+///
+/// The caller pays the fees by approving the contract to spend the tokens through pallet-assets,
+/// after which the contract transfers them to itself through `transfer_approved`.
+#[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FeeAsset {
+	/// The indices of pallet-assets and of its `transfer_approved` call in the runtime of the
+	/// chain on which the contract is deployed.
+	pub indices: AssetsCallIndices,
+	/// The id of the relay chain token in pallet-assets.
+	#[cfg_attr(feature = "serde", serde(with = "primitives::serde_utils::u128_string"))]
+	pub asset_id: AssetId,
+}
+
+/// The configuration for sending regions back to the Coretime chain over XCM.
+#[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TeleportConfig {
+	/// The indices of pallet-xcm and of its `execute` call in the runtime of the chain on which
+	/// the contract is deployed.
+	pub indices: XcmCallIndices,
+	/// The parachain id of the Coretime chain. Regions can only be sent back to it.
+	pub coretime_para_id: ParaId,
+	/// The index of pallet-broker in the runtime of the Coretime chain.
+	pub broker_pallet_index: u8,
+	/// The amount of relay chain tokens paid for the execution on the Coretime chain. The caller
+	/// of `remove_and_teleport` pays this amount to the contract.
+	#[cfg_attr(feature = "serde", serde(with = "primitives::serde_utils::u128_string"))]
+	pub fee: Balance,
+	/// The relay chain token in which the fee is paid.
+	pub fee_asset: FeeAsset,
+	/// The maximum weight of the local execution of the XCM, as `(ref_time, proof_size)`.
+	pub max_weight: (u64, u64),
+}
+
+/// The configuration for dispatching calls on the Coretime chain over XCM `Transact`.
+///
+/// The calls are dispatched from the account the Coretime chain derives from the location of the
+/// contract. The regions held by the contract are owned by the sovereign account of this chain on
+/// the Coretime chain, so each region is first moved to the account of the contract there.
+#[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TransactConfig {
+	/// The indices of pallet-xcm and of its `send` and `execute` calls in the runtime of the
+	/// chain on which the contract is deployed.
+	pub xcm_indices: XcmCallIndices,
+	/// The parachain id of the chain on which the contract is deployed.
+	pub para_id: ParaId,
+	/// The parachain id of the Coretime chain.
+	pub coretime_para_id: ParaId,
+	/// The indices of pallet-broker and of its calls in the runtime of the Coretime chain.
+	pub broker_indices: BrokerCallIndices,
+	/// The indices of pallet-xcm and of its `execute` call in the runtime of the Coretime chain,
+	/// used for sending regions back to this chain.
+	pub coretime_xcm_indices: XcmCallIndices,
+	/// The amount of relay chain tokens paid for the execution of each XCM sent by the contract.
+	/// The caller pays this amount to the contract for each XCM sent on its behalf.
+	#[cfg_attr(feature = "serde", serde(with = "primitives::serde_utils::u128_string"))]
+	pub fee: Balance,
+	/// The relay chain token in which the fee is paid.
+	pub fee_asset: FeeAsset,
+	/// The weight of the dispatched calls, as `(ref_time, proof_size)`.
+	pub transact_weight: (u64, u64),
+	/// The maximum weight of the execution of the XCM moving a region between the accounts of
+	/// the contract on this chain and on the Coretime chain, as `(ref_time, proof_size)`.
+	pub max_weight: (u64, u64),
+	/// The number of blocks that must pass after an assignment is requested before the region
+	/// can be reassigned.
+	pub reassignment_delay: BlockNumber,
+}
+
+/// The task to which a wrapped region got assigned through the contract.
+#[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Assignment {
+	/// The parachain to which the region is assigned.
+	pub task: ParaId,
+	/// Whether the assignment can still be changed. Finally assigned regions can't be
+	/// transferred.
+	pub finality: Finality,
+	/// Whether the assignment got confirmed to have succeeded on the Coretime chain.
+	pub confirmed: bool,
+	/// The block at which the assignment got requested.
+	pub requested_at: BlockNumber,
+}
+
+/// A wrapped region contributed to the instantaneous coretime pool through the contract.
+#[derive(scale::Decode, scale::Encode, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PooledRegion {
+	/// The account on the Coretime chain to which the revenue of the region is paid, which is the
+	/// owner of the wrapped region.
+	#[cfg_attr(feature = "serde", serde(with = "primitives::serde_utils::account_id"))]
+	pub payee: AccountId,
+	/// The first timeslice whose revenue hasn't been claimed yet.
+	///
+	/// pallet-broker keys the contribution of the region by a region id beginning at this
+	/// timeslice.
+	pub claimed_until: Timeslice,
+	/// The timeslice up to which the claim awaiting confirmation attempted to claim the revenue,
+	/// if any.
+	pub pending_claim: Option<Timeslice>,
+}
+
+/// The configuration for reaping expired regions.
+#[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReapConfig {
+	/// The reward paid out of the reap reward pool for each reaped region.
+	#[cfg_attr(feature = "serde", serde(with = "primitives::serde_utils::u128_string"))]
+	pub reward: Balance,
+}
+
+/// The status of a region relative to the current timeslice.
+#[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RegionStatus {
+	/// The region begins in the future.
+	Inactive,
+	/// The current timeslice is within the region.
+	Active,
+	/// The region ended.
+	Expired,
+}
+
+impl RegionStatus {
+	/// Returns the status of `region` at `timeslice`.
+	pub fn of(region: &Region, timeslice: Timeslice) -> Self {
+		if timeslice < region.begin {
+			RegionStatus::Inactive
+		} else if timeslice < region.end {
+			RegionStatus::Active
+		} else {
+			RegionStatus::Expired
+		}
+	}
+}
+
+/// The criteria the regions returned by `regions_of` must match. Criteria which aren't set match
+/// any region.
+#[derive(scale::Decode, scale::Encode, Default, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RegionFilter {
+	/// The core the region must belong to.
+	pub core: Option<CoreIndex>,
+	/// The status the region must have at the current timeslice.
+	pub status: Option<RegionStatus>,
+	/// The earliest timeslice at which the region may begin.
+	pub min_begin: Option<Timeslice>,
+	/// The latest timeslice at which the region may end.
+	pub max_end: Option<Timeslice>,
+}
+
+impl RegionFilter {
+	/// Returns whether `region` matches all the criteria of the filter at `timeslice`.
+	pub fn matches(&self, region: &Region, timeslice: Timeslice) -> bool {
+		self.core.map_or(true, |core| region.core == core) &&
+			self.status.map_or(true, |status| RegionStatus::of(region, timeslice) == status) &&
+			self.min_begin.map_or(true, |min_begin| region.begin >= min_begin) &&
+			self.max_end.map_or(true, |max_end| region.end <= max_end)
+	}
+}
+
+/// A page of the regions owned by an account, as returned by `regions_of`.
+#[derive(scale::Decode, scale::Encode, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct RegionsPage {
+	/// The regions of the page along with their metadata.
+	pub regions: Vec<(Id, VersionedRegion)>,
+	/// The cursor from which the next page starts, or `None` if this is the last page.
+	pub next: Option<u128>,
+}
+
+/// An entry of the metadata history of a region.
+#[derive(scale::Decode, scale::Encode, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MetadataRecord {
+	/// The version the metadata was stored with.
+	pub version: Version,
+	/// The metadata of the region.
+	pub region: Region,
+	/// The account which caused the metadata to be stored, e.g. by initializing the region or by
+	/// successfully challenging its metadata.
+	#[cfg_attr(feature = "serde", serde(with = "primitives::serde_utils::account_id"))]
+	pub initializer: AccountId,
+	/// The block at which the metadata was stored.
+	pub block: BlockNumber,
+}
+
+/// A page of the metadata history of a region, as returned by `metadata_history`.
+#[derive(scale::Decode, scale::Encode, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct MetadataHistoryPage {
+	/// The entries of the page, from the oldest to the newest.
+	pub records: Vec<MetadataRecord>,
+	/// The cursor from which the next page starts, or `None` if this is the last page.
+	pub next: Option<u32>,
+}
+
+/// The evidence showing that the metadata of an optimistically initialized region is incorrect.
+#[derive(scale::Decode, scale::Encode, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum ChallengeEvidence {
+	/// A storage proof of the region record on the Coretime chain, see `init_with_proof`.
+	Proof { state_root_block: BlockNumber, proof: Vec<Vec<u8>> },
+	/// The actual end of the region, as determined by the arbiter. Only accepted from the
+	/// arbiter.
+	Verdict { end: Timeslice },
+}
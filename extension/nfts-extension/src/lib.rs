@@ -0,0 +1,120 @@
+// This file is part of RegionX.
+//
+// RegionX is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// RegionX is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with RegionX.  If not, see <https://www.gnu.org/licenses/>.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use openbrush::traits::AccountId;
+use primitives::{
+	coretime::RawRegionId,
+	nfts::{CollectionDetails, CollectionSettings, ItemDetails, ItemSettings},
+	uniques::CollectionId,
+};
+use scale::{Decode, Encode};
+
+/// The pallet-nfts counterpart of the `UniquesExtension`. Similarly, this only contains the state
+/// reads which are essential for the xc-regions contract.
+pub trait NftsExtension {
+	/// The owner of the specific item.
+	fn owner(
+		&self,
+		collection_id: CollectionId,
+		item_id: RawRegionId,
+	) -> Result<Option<AccountId>, NftsError> {
+		::ink::env::chain_extension::ChainExtensionMethod::build(0x60001)
+			.input::<(CollectionId, RawRegionId)>()
+			.output::<Result<Option<AccountId>, NftsError>, true>()
+			.handle_error_code::<NftsError>()
+			.call(&(collection_id, item_id))
+	}
+
+	/// Returns the details of a collection.
+	fn collection(
+		&self,
+		collection_id: CollectionId,
+	) -> Result<Option<CollectionDetails>, NftsError> {
+		::ink::env::chain_extension::ChainExtensionMethod::build(0x60006)
+			.input::<CollectionId>()
+			.output::<Result<Option<CollectionDetails>, NftsError>, true>()
+			.handle_error_code::<NftsError>()
+			.call(&collection_id)
+	}
+
+	/// Returns the details of an item within a collection.
+	fn item(
+		&self,
+		collection_id: CollectionId,
+		item_id: RawRegionId,
+	) -> Result<Option<ItemDetails>, NftsError> {
+		::ink::env::chain_extension::ChainExtensionMethod::build(0x60007)
+			.input::<(CollectionId, RawRegionId)>()
+			.output::<Result<Option<ItemDetails>, NftsError>, true>()
+			.handle_error_code::<NftsError>()
+			.call(&(collection_id, item_id))
+	}
+
+	/// Returns the settings of an item within a collection.
+	fn item_settings(
+		&self,
+		collection_id: CollectionId,
+		item_id: RawRegionId,
+	) -> Result<Option<ItemSettings>, NftsError> {
+		::ink::env::chain_extension::ChainExtensionMethod::build(0x60008)
+			.input::<(CollectionId, RawRegionId)>()
+			.output::<Result<Option<ItemSettings>, NftsError>, true>()
+			.handle_error_code::<NftsError>()
+			.call(&(collection_id, item_id))
+	}
+
+	/// Returns the settings of a collection.
+	fn collection_settings(
+		&self,
+		collection_id: CollectionId,
+	) -> Result<Option<CollectionSettings>, NftsError> {
+		::ink::env::chain_extension::ChainExtensionMethod::build(0x60009)
+			.input::<CollectionId>()
+			.output::<Result<Option<CollectionSettings>, NftsError>, true>()
+			.handle_error_code::<NftsError>()
+			.call(&collection_id)
+	}
+}
+
+#[derive(PartialEq, Eq, Copy, Clone, Encode, Decode, Debug)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum NftsError {
+	/// Origin Caller is not supported
+	OriginCannotBeCaller = 98,
+	/// Unknown error
+	RuntimeError = 99,
+	/// Unknow status code
+	UnknownStatusCode,
+	/// Encountered unexpected invalid SCALE encoding
+	InvalidScaleEncoding,
+}
+
+impl ink::env::chain_extension::FromStatusCode for NftsError {
+	fn from_status_code(status_code: u32) -> Result<(), Self> {
+		match status_code {
+			0 => Ok(()),
+			98 => Err(Self::OriginCannotBeCaller),
+			99 => Err(Self::RuntimeError),
+			_ => Err(Self::UnknownStatusCode),
+		}
+	}
+}
+
+impl From<scale::Error> for NftsError {
+	fn from(_: scale::Error) -> Self {
+		NftsError::InvalidScaleEncoding
+	}
+}
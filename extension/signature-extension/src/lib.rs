@@ -0,0 +1,82 @@
+// This file is part of RegionX.
+//
+// RegionX is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// RegionX is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with RegionX.  If not, see <https://www.gnu.org/licenses/>.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink::prelude::vec::Vec;
+use scale::{Decode, Encode};
+
+/// ink! 4 only exposes the ecdsa host functions to contracts, so the runtime verifies sr25519 and
+/// ed25519 signatures on behalf of the contract through this chain extension. The runtime is
+/// expected to use the `sr25519_verify` and `ed25519_verify` host functions of `sp_io::crypto`.
+pub trait SignatureVerifierExtension {
+	/// Returns whether `signature` is a valid sr25519 signature of `message` by `public`, made in
+	/// the `substrate` signing context.
+	fn sr25519_verify(
+		&self,
+		signature: [u8; 64],
+		message: Vec<u8>,
+		public: [u8; 32],
+	) -> Result<bool, SignatureVerifierError> {
+		::ink::env::chain_extension::ChainExtensionMethod::build(0x80001)
+			.input::<([u8; 64], Vec<u8>, [u8; 32])>()
+			.output::<Result<bool, SignatureVerifierError>, true>()
+			.handle_error_code::<SignatureVerifierError>()
+			.call(&(signature, message, public))
+	}
+
+	/// Returns whether `signature` is a valid ed25519 signature of `message` by `public`.
+	fn ed25519_verify(
+		&self,
+		signature: [u8; 64],
+		message: Vec<u8>,
+		public: [u8; 32],
+	) -> Result<bool, SignatureVerifierError> {
+		::ink::env::chain_extension::ChainExtensionMethod::build(0x80002)
+			.input::<([u8; 64], Vec<u8>, [u8; 32])>()
+			.output::<Result<bool, SignatureVerifierError>, true>()
+			.handle_error_code::<SignatureVerifierError>()
+			.call(&(signature, message, public))
+	}
+}
+
+#[derive(PartialEq, Eq, Copy, Clone, Encode, Decode, Debug)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum SignatureVerifierError {
+	/// Origin Caller is not supported
+	OriginCannotBeCaller = 98,
+	/// Unknown error
+	RuntimeError = 99,
+	/// Unknow status code
+	UnknownStatusCode,
+	/// Encountered unexpected invalid SCALE encoding
+	InvalidScaleEncoding,
+}
+
+impl ink::env::chain_extension::FromStatusCode for SignatureVerifierError {
+	fn from_status_code(status_code: u32) -> Result<(), Self> {
+		match status_code {
+			0 => Ok(()),
+			98 => Err(Self::OriginCannotBeCaller),
+			99 => Err(Self::RuntimeError),
+			_ => Err(Self::UnknownStatusCode),
+		}
+	}
+}
+
+impl From<scale::Error> for SignatureVerifierError {
+	fn from(_: scale::Error) -> Self {
+		SignatureVerifierError::InvalidScaleEncoding
+	}
+}
@@ -21,4 +21,7 @@
 pub struct Extension;
 
 impl uniques_extension::UniquesExtension for Extension {}
+impl nfts_extension::NftsExtension for Extension {}
 impl block_number_extension::BlockNumberProviderExtension for Extension {}
+impl state_root_extension::StateRootProviderExtension for Extension {}
+impl signature_extension::SignatureVerifierExtension for Extension {}
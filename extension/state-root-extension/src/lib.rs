@@ -0,0 +1,74 @@
+// This file is part of RegionX.
+//
+// RegionX is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// RegionX is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with RegionX.  If not, see <https://www.gnu.org/licenses/>.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use openbrush::traits::{BlockNumber, Hash};
+use scale::{Decode, Encode};
+
+pub trait StateRootProviderExtension {
+	/// The state root of the Coretime chain at the given Coretime chain block number.
+	///
+	/// Returns `None` if the runtime doesn't know the state root of the block, e.g. because the
+	/// block is too old or not yet finalized.
+	fn coretime_state_root(
+		&self,
+		block: BlockNumber,
+	) -> Result<Option<Hash>, StateRootProviderError> {
+		::ink::env::chain_extension::ChainExtensionMethod::build(0x70001)
+			.input::<BlockNumber>()
+			.output::<Result<Option<Hash>, StateRootProviderError>, true>()
+			.handle_error_code::<StateRootProviderError>()
+			.call(&block)
+	}
+
+	/// The latest Coretime chain block number whose state root is known to the runtime.
+	fn latest_coretime_block(&self) -> Result<BlockNumber, StateRootProviderError> {
+		::ink::env::chain_extension::ChainExtensionMethod::build(0x70002)
+			.input::<()>()
+			.output::<Result<BlockNumber, StateRootProviderError>, true>()
+			.handle_error_code::<StateRootProviderError>()
+			.call(&())
+	}
+}
+
+#[derive(PartialEq, Eq, Copy, Clone, Encode, Decode, Debug)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum StateRootProviderError {
+	/// Origin Caller is not supported
+	OriginCannotBeCaller = 98,
+	/// Unknown error
+	RuntimeError = 99,
+	/// Unknow status code
+	UnknownStatusCode,
+	/// Encountered unexpected invalid SCALE encoding
+	InvalidScaleEncoding,
+}
+
+impl ink::env::chain_extension::FromStatusCode for StateRootProviderError {
+	fn from_status_code(status_code: u32) -> Result<(), Self> {
+		match status_code {
+			0 => Ok(()),
+			98 => Err(Self::OriginCannotBeCaller),
+			99 => Err(Self::RuntimeError),
+			_ => Err(Self::UnknownStatusCode),
+		}
+	}
+}
+
+impl From<scale::Error> for StateRootProviderError {
+	fn from(_: scale::Error) -> Self {
+		StateRootProviderError::InvalidScaleEncoding
+	}
+}
@@ -0,0 +1,63 @@
+// This file is part of RegionX.
+//
+// RegionX is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// RegionX is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with RegionX.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::Balance;
+use openbrush::traits::AccountId;
+use scale::{Compact, Encode, Output};
+use sp_runtime::MultiAddress;
+
+/// The type used to identify assets in pallet-assets.
+pub type AssetId = u128;
+
+/// The indices of pallet-assets and of its calls within the runtime.
+#[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AssetsCallIndices {
+	/// The index of pallet-assets in the runtime.
+	pub pallet: u8,
+	/// The index of the `transfer_approved` call.
+	pub transfer_approved: u8,
+}
+
+impl Default for AssetsCallIndices {
+	/// The indices used in the Astar runtime.
+	fn default() -> Self {
+		Self { pallet: 36, transfer_approved: 25 }
+	}
+}
+
+pub enum AssetsCall {
+	TransferApproved {
+		id: AssetId,
+		owner: MultiAddress<AccountId, ()>,
+		destination: MultiAddress<AccountId, ()>,
+		amount: Balance,
+	},
+}
+
+impl AssetsCall {
+	/// Encodes the call index followed by the arguments of the call.
+	///
+	/// Just like in the pallet, the asset id and the amount are compact encoded.
+	pub fn encode_to<T: Output + ?Sized>(&self, indices: &AssetsCallIndices, output: &mut T) {
+		match self {
+			AssetsCall::TransferApproved { id, owner, destination, amount } => {
+				output.push_byte(indices.transfer_approved);
+				(Compact(*id), owner, destination, Compact(*amount)).encode_to(output);
+			},
+		}
+	}
+}
@@ -0,0 +1,66 @@
+// This file is part of RegionX.
+//
+// RegionX is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// RegionX is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with RegionX.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::coretime::{Finality, ParaId, RegionId, Timeslice};
+use openbrush::traits::AccountId;
+use scale::{Encode, Output};
+
+/// The indices of pallet-broker and of its calls within the runtime of the Coretime chain.
+#[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BrokerCallIndices {
+	/// The index of pallet-broker in the runtime.
+	pub pallet: u8,
+	/// The index of the `assign` call.
+	pub assign: u8,
+	/// The index of the `pool` call.
+	pub pool: u8,
+	/// The index of the `claim_revenue` call.
+	pub claim_revenue: u8,
+}
+
+impl Default for BrokerCallIndices {
+	/// The indices used in the Kusama Coretime chain runtime.
+	fn default() -> Self {
+		Self { pallet: 50, assign: 10, pool: 11, claim_revenue: 12 }
+	}
+}
+
+pub enum BrokerCall {
+	Assign { region_id: RegionId, task: ParaId, finality: Finality },
+	Pool { region_id: RegionId, payee: AccountId, finality: Finality },
+	ClaimRevenue { region_id: RegionId, max_timeslices: Timeslice },
+}
+
+impl BrokerCall {
+	/// Encodes the call index followed by the arguments of the call.
+	pub fn encode_to<T: Output + ?Sized>(&self, indices: &BrokerCallIndices, output: &mut T) {
+		match self {
+			BrokerCall::Assign { region_id, task, finality } => {
+				output.push_byte(indices.assign);
+				(region_id, task, finality).encode_to(output);
+			},
+			BrokerCall::Pool { region_id, payee, finality } => {
+				output.push_byte(indices.pool);
+				(region_id, payee, finality).encode_to(output);
+			},
+			BrokerCall::ClaimRevenue { region_id, max_timeslices } => {
+				output.push_byte(indices.claim_revenue);
+				(region_id, max_timeslices).encode_to(output);
+			},
+		}
+	}
+}
@@ -0,0 +1,197 @@
+// This file is part of RegionX.
+//
+// RegionX is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// RegionX is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with RegionX.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Conversions between relay chain blocks, timeslices, bulk periods and sale phases.
+//!
+//! All the computations are based on the configuration of pallet-broker, which is mirrored by the
+//! `BrokerConfig` struct.
+
+use crate::coretime::Timeslice;
+use openbrush::traits::{BlockNumber, Timestamp};
+
+/// Duration of a timeslice in rc blocks.
+pub const TIMESLICE_PERIOD: BlockNumber = 80;
+
+/// The expected duration of a relay chain block in milliseconds.
+pub const RELAY_BLOCK_TIME: Timestamp = 6000;
+
+/// The timeslice in which the relay chain block `block` is.
+///
+/// Returns zero if the `timeslice_period` is zero.
+pub fn block_to_timeslice(block: BlockNumber, timeslice_period: BlockNumber) -> Timeslice {
+	block.checked_div(timeslice_period).unwrap_or_default()
+}
+
+/// The relay chain block at which the timeslice `timeslice` starts.
+pub fn timeslice_to_block(timeslice: Timeslice, timeslice_period: BlockNumber) -> BlockNumber {
+	timeslice.saturating_mul(timeslice_period)
+}
+
+/// The configuration of pallet-broker.
+///
+/// This only contains the parts of the pallet's `ConfigRecord` and constants that are relevant
+/// for time related computations.
+#[derive(scale::Decode, scale::Encode, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+pub struct BrokerConfig {
+	/// The duration of a timeslice in relay chain blocks.
+	pub timeslice_period: BlockNumber,
+	/// The length in timeslices of the regions which are up for sale in a bulk sale.
+	pub region_length: Timeslice,
+	/// The length in Coretime chain blocks of the interlude period, during which only renewals
+	/// are possible.
+	pub interlude_length: BlockNumber,
+	/// The length in Coretime chain blocks of the leadin period, during which the price
+	/// decreases towards the regular price.
+	pub leadin_length: BlockNumber,
+	/// The expected duration of a relay chain block in milliseconds.
+	pub relay_block_time: Timestamp,
+}
+
+impl Default for BrokerConfig {
+	/// The configuration used on the Kusama and Polkadot Coretime chains.
+	fn default() -> Self {
+		Self {
+			timeslice_period: TIMESLICE_PERIOD,
+			region_length: 5040,
+			interlude_length: 50400,
+			leadin_length: 50400,
+			relay_block_time: RELAY_BLOCK_TIME,
+		}
+	}
+}
+
+/// A bulk period, i.e. the time span covered by the regions sold in a single bulk sale.
+#[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct BulkPeriod {
+	/// The timeslice at which the bulk period starts.
+	pub begin: Timeslice,
+	/// The timeslice at which the bulk period ends.
+	pub end: Timeslice,
+}
+
+/// The phases a bulk sale goes through.
+#[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum SalePhase {
+	/// Only renewals of the regions from the previous bulk period are possible.
+	Interlude,
+	/// Coretime can be purchased at a price decreasing towards the regular price.
+	Leadin,
+	/// Coretime can be purchased at the regular price.
+	FixedPrice,
+}
+
+impl BrokerConfig {
+	/// The timeslice in which the relay chain block `block` is.
+	pub fn timeslice_at(&self, block: BlockNumber) -> Timeslice {
+		block_to_timeslice(block, self.timeslice_period)
+	}
+
+	/// The relay chain block at which the timeslice `timeslice` starts.
+	pub fn timeslice_start(&self, timeslice: Timeslice) -> BlockNumber {
+		timeslice_to_block(timeslice, self.timeslice_period)
+	}
+
+	/// The bulk period containing `timeslice`.
+	///
+	/// Bulk periods are aligned to `anchor`, which can be the beginning of any known bulk period,
+	/// e.g. the `region_begin` of the ongoing sale.
+	///
+	/// Returns `None` if the bulk period containing `timeslice` would begin before timeslice
+	/// zero.
+	pub fn bulk_period(&self, anchor: Timeslice, timeslice: Timeslice) -> Option<BulkPeriod> {
+		let length = self.region_length.max(1);
+
+		let begin = if timeslice >= anchor {
+			let elapsed = (timeslice - anchor) / length;
+			anchor.checked_add(elapsed.checked_mul(length)?)?
+		} else {
+			let remaining = (anchor - timeslice).div_ceil(length);
+			anchor.checked_sub(remaining.checked_mul(length)?)?
+		};
+
+		Some(BulkPeriod { begin, end: begin.checked_add(length)? })
+	}
+
+	/// The Coretime chain block at which the interlude period of a sale starts.
+	///
+	/// ## Arguments:
+	/// - `sale_start`: The Coretime chain block at which the sale starts, as stored in
+	///   pallet-broker's `SaleInfo`. This is the block at which the interlude period ends.
+	pub fn interlude_start(&self, sale_start: BlockNumber) -> BlockNumber {
+		sale_start.saturating_sub(self.interlude_length)
+	}
+
+	/// The phase of a bulk sale at the Coretime chain block `now`.
+	///
+	/// The interlude period lasts `interlude_length` blocks up to `sale_start`, and is followed
+	/// by the leadin period lasting `leadin_length` blocks. The sale is at the regular price
+	/// afterwards.
+	///
+	/// Returns `None` if `now` is before the interlude period of the sale, i.e. while the
+	/// previous sale is still ongoing.
+	///
+	/// ## Arguments:
+	/// - `sale_start`: The Coretime chain block at which the sale starts, as stored in
+	///   pallet-broker's `SaleInfo`.
+	/// - `now`: The current Coretime chain block.
+	pub fn sale_phase(&self, sale_start: BlockNumber, now: BlockNumber) -> Option<SalePhase> {
+		if now < self.interlude_start(sale_start) {
+			None
+		} else if now < sale_start {
+			Some(SalePhase::Interlude)
+		} else if now < sale_start.saturating_add(self.leadin_length) {
+			Some(SalePhase::Leadin)
+		} else {
+			Some(SalePhase::FixedPrice)
+		}
+	}
+
+	/// The estimated timestamp in milliseconds at which the relay chain block `block` is
+	/// produced.
+	///
+	/// ## Arguments:
+	/// - `now`: The latest known relay chain block.
+	/// - `now_timestamp`: The timestamp of the latest known relay chain block.
+	/// - `block`: The relay chain block for which the timestamp is estimated.
+	pub fn estimate_timestamp(
+		&self,
+		now: BlockNumber,
+		now_timestamp: Timestamp,
+		block: BlockNumber,
+	) -> Timestamp {
+		if block >= now {
+			let elapsed = Timestamp::from(block - now).saturating_mul(self.relay_block_time);
+			now_timestamp.saturating_add(elapsed)
+		} else {
+			let elapsed = Timestamp::from(now - block).saturating_mul(self.relay_block_time);
+			now_timestamp.saturating_sub(elapsed)
+		}
+	}
+
+	/// The estimated timestamp in milliseconds at which the timeslice `timeslice` starts.
+	///
+	/// See `estimate_timestamp` for the description of `now` and `now_timestamp`.
+	pub fn estimate_timeslice_timestamp(
+		&self,
+		now: BlockNumber,
+		now_timestamp: Timestamp,
+		timeslice: Timeslice,
+	) -> Timestamp {
+		self.estimate_timestamp(now, now_timestamp, self.timeslice_start(timeslice))
+	}
+}
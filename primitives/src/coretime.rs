@@ -13,7 +13,13 @@
 // You should have received a copy of the GNU General Public License
 // along with RegionX.  If not, see <https://www.gnu.org/licenses/>.
 
-use openbrush::traits::BlockNumber;
+use crate::{ensure, Balance};
+use core::{
+	fmt,
+	ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not},
+	str::FromStr,
+};
+use openbrush::traits::AccountId;
 
 /// The type used for identifying regions.
 ///
@@ -26,15 +32,19 @@ pub type Timeslice = u32;
 /// Index of a Polkadot Core.
 pub type CoreIndex = u16;
 
-/// Duration of a timeslice in rc blocks.
-pub const TIMESLICE_PERIOD: BlockNumber = 80;
+/// The identifier of a task, i.e. a parachain, to which a region can be assigned.
+pub type ParaId = u32;
+
+/// Duration of a timeslice in rc blocks. Kept here for backwards compatibility, see
+/// `calendar::TIMESLICE_PERIOD`.
+pub use crate::calendar::TIMESLICE_PERIOD;
 
 /// The bit length of a core mask.
 pub const CORE_MASK_BIT_LEN: usize = 80;
 
 /// All Regions are also associated with a Core Mask, an 80-bit bitmap, to denote the regularity at
 /// which it may be scheduled on the core.
-#[derive(scale::Decode, scale::Encode, Default, Clone, Debug, PartialEq, Eq)]
+#[derive(scale::Decode, scale::Encode, Default, Clone, Copy, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
 pub struct CoreMask([u8; 10]);
 
@@ -45,6 +55,12 @@ impl CoreMask {
 	pub fn complete() -> Self {
 		Self([255u8; 10])
 	}
+	pub fn is_void(&self) -> bool {
+		self.0 == [0u8; 10]
+	}
+	pub fn is_complete(&self) -> bool {
+		self.0 == [255u8; 10]
+	}
 	pub fn count_zeros(&self) -> u32 {
 		self.0.iter().map(|i| i.count_zeros()).sum()
 	}
@@ -58,6 +74,30 @@ impl CoreMask {
 		}
 		Self(v)
 	}
+	/// Returns whether the bit at `index` is set. Bits are indexed starting from the most
+	/// significant one, consistent with `from_chunk`.
+	pub fn is_set(&self, index: u32) -> bool {
+		let index = index as usize;
+		index < CORE_MASK_BIT_LEN && self.0[index / 8] & (128 >> (index % 8)) != 0
+	}
+	/// Returns an iterator over the indices of all set bits in ascending order.
+	pub fn iter_ones(&self) -> impl Iterator<Item = u32> {
+		let mask = *self;
+		(0..CORE_MASK_BIT_LEN as u32).filter(move |i| mask.is_set(*i))
+	}
+	/// Returns an iterator over the indices of all unset bits in ascending order.
+	pub fn iter_zeros(&self) -> impl Iterator<Item = u32> {
+		let mask = *self;
+		(0..CORE_MASK_BIT_LEN as u32).filter(move |i| !mask.is_set(*i))
+	}
+	/// Returns whether all the bits set in `self` are also set in `other`.
+	pub fn is_subset_of(&self, other: &CoreMask) -> bool {
+		(*self & !*other).is_void()
+	}
+	/// Returns whether `self` and `other` have no set bits in common.
+	pub fn is_disjoint(&self, other: &CoreMask) -> bool {
+		(*self & *other).is_void()
+	}
 }
 
 impl From<u128> for CoreMask {
@@ -75,10 +115,110 @@ impl From<CoreMask> for u128 {
 		x.0.into_iter().fold(0u128, |a, i| a << 8 | i as u128)
 	}
 }
+impl BitAnd for CoreMask {
+	type Output = Self;
+	fn bitand(mut self, rhs: Self) -> Self {
+		self.bitand_assign(rhs);
+		self
+	}
+}
+impl BitAndAssign for CoreMask {
+	fn bitand_assign(&mut self, rhs: Self) {
+		self.0.iter_mut().zip(rhs.0).for_each(|(a, b)| *a &= b);
+	}
+}
+impl BitOr for CoreMask {
+	type Output = Self;
+	fn bitor(mut self, rhs: Self) -> Self {
+		self.bitor_assign(rhs);
+		self
+	}
+}
+impl BitOrAssign for CoreMask {
+	fn bitor_assign(&mut self, rhs: Self) {
+		self.0.iter_mut().zip(rhs.0).for_each(|(a, b)| *a |= b);
+	}
+}
+impl BitXor for CoreMask {
+	type Output = Self;
+	fn bitxor(mut self, rhs: Self) -> Self {
+		self.bitxor_assign(rhs);
+		self
+	}
+}
+impl BitXorAssign for CoreMask {
+	fn bitxor_assign(&mut self, rhs: Self) {
+		self.0.iter_mut().zip(rhs.0).for_each(|(a, b)| *a ^= b);
+	}
+}
+impl Not for CoreMask {
+	type Output = Self;
+	fn not(self) -> Self {
+		Self(self.0.map(|i| !i))
+	}
+}
+
+/// Formats the mask as a string of 80 binary digits, starting from the most significant bit.
+impl fmt::Display for CoreMask {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		self.0.iter().try_for_each(|byte| write!(f, "{:08b}", byte))
+	}
+}
+
+/// Formats the mask as 20 hexadecimal digits. The alternate flag (`{:#x}`) adds a `0x` prefix.
+impl fmt::LowerHex for CoreMask {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		if f.alternate() {
+			write!(f, "0x")?;
+		}
+		self.0.iter().try_for_each(|byte| write!(f, "{:02x}", byte))
+	}
+}
+
+/// Parses a mask either from 80 binary digits, or from `0x` followed by 20 hexadecimal digits.
+impl FromStr for CoreMask {
+	type Err = ParseRegionError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		if let Some(hex) = s.strip_prefix("0x") {
+			return parse_hex(hex, CORE_MASK_BIT_LEN / 4)
+				.map(CoreMask::from)
+				.ok_or(ParseRegionError::InvalidMask)
+		}
+
+		ensure!(s.len() == CORE_MASK_BIT_LEN, ParseRegionError::InvalidMask);
+		let mut v = [0u8; 10];
+		for (i, bit) in s.bytes().enumerate() {
+			match bit {
+				b'0' => (),
+				b'1' => v[i / 8] |= 128 >> (i % 8),
+				_ => return Err(ParseRegionError::InvalidMask),
+			}
+		}
+		Ok(Self(v))
+	}
+}
+
+/// Serializes the mask in its `0x` prefixed hexadecimal form.
+#[cfg(feature = "serde")]
+impl serde::Serialize for CoreMask {
+	fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.collect_str(&format_args!("{:#x}", self))
+	}
+}
+
+/// Deserializes the mask from any of the forms accepted by its `FromStr` implementation.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for CoreMask {
+	fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		crate::serde_utils::deserialize_from_str(deserializer)
+	}
+}
 
 /// Self-describing identity for a Region of Bulk Coretime.
-#[derive(scale::Decode, scale::Encode, Default, Clone, Debug, PartialEq, Eq)]
+#[derive(scale::Decode, scale::Encode, Default, Clone, Copy, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RegionId {
 	/// The timeslice at which the region starts.
 	pub begin: Timeslice,
@@ -100,8 +240,101 @@ impl From<RegionId> for RawRegionId {
 	}
 }
 
+/// Formats the region id as `begin:core:mask`, with the mask in its hexadecimal form. E.g.
+/// `4:2:0xffffffffff0000000000`.
+impl fmt::Display for RegionId {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}:{}:{:#x}", self.begin, self.core, self.mask)
+	}
+}
+
+/// Formats the canonical hexadecimal form of the corresponding `RawRegionId`, which is always
+/// 32 digits long. The alternate flag (`{:#x}`) adds a `0x` prefix.
+impl fmt::LowerHex for RegionId {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		if f.alternate() {
+			write!(f, "0x")?;
+		}
+		write!(f, "{:032x}", RawRegionId::from(*self))
+	}
+}
+
+/// Parses a region id either from the `begin:core:mask` form, where the mask can be in any of the
+/// forms accepted by `CoreMask`, or from the canonical hexadecimal form of the raw region id
+/// prefixed with `0x`.
+impl FromStr for RegionId {
+	type Err = ParseRegionError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		if let Some(hex) = s.strip_prefix("0x") {
+			return parse_hex(hex, 32).map(RegionId::from).ok_or(ParseRegionError::InvalidRawId)
+		}
+
+		let mut parts = s.split(':');
+		let (Some(begin), Some(core), Some(mask), None) =
+			(parts.next(), parts.next(), parts.next(), parts.next())
+		else {
+			return Err(ParseRegionError::InvalidFormat)
+		};
+
+		Ok(RegionId {
+			begin: parse_decimal(begin).ok_or(ParseRegionError::InvalidBegin)?,
+			core: parse_decimal(core).ok_or(ParseRegionError::InvalidCore)?,
+			mask: mask.parse()?,
+		})
+	}
+}
+
+/// Errors that can occur when parsing the textual form of a `RegionId` or a `CoreMask`.
+#[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum ParseRegionError {
+	/// The region id is neither in the `begin:core:mask` form nor a `0x` prefixed raw id.
+	InvalidFormat,
+	/// The begin of the region is not a valid timeslice.
+	InvalidBegin,
+	/// The core of the region is not a valid core index.
+	InvalidCore,
+	/// The mask is neither 80 binary digits nor `0x` followed by 20 hexadecimal digits.
+	InvalidMask,
+	/// The raw region id is not `0x` followed by 32 hexadecimal digits.
+	InvalidRawId,
+}
+
+impl fmt::Display for ParseRegionError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			ParseRegionError::InvalidFormat =>
+				write!(f, "expected `begin:core:mask` or a `0x` prefixed raw region id"),
+			ParseRegionError::InvalidBegin => write!(f, "begin is not a valid timeslice"),
+			ParseRegionError::InvalidCore => write!(f, "core is not a valid core index"),
+			ParseRegionError::InvalidMask =>
+				write!(f, "expected 80 binary digits or `0x` followed by 20 hex digits as mask"),
+			ParseRegionError::InvalidRawId =>
+				write!(f, "expected `0x` followed by 32 hex digits as raw region id"),
+		}
+	}
+}
+
+/// Parses exactly `len` hexadecimal digits.
+fn parse_hex(s: &str, len: usize) -> Option<u128> {
+	if s.len() != len || !s.bytes().all(|c| c.is_ascii_hexdigit()) {
+		return None
+	}
+	u128::from_str_radix(s, 16).ok()
+}
+
+/// Parses a non-empty string of decimal digits, without any sign.
+fn parse_decimal<T: FromStr>(s: &str) -> Option<T> {
+	if s.is_empty() || !s.bytes().all(|c| c.is_ascii_digit()) {
+		return None
+	}
+	s.parse().ok()
+}
+
 #[derive(scale::Decode, scale::Encode, Default, Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Region {
 	/// The timeslice at which the reigon starts.
 	pub begin: Timeslice,
@@ -112,3 +345,119 @@ pub struct Region {
 	/// The regularity parts in which this Region will be scheduled.
 	pub mask: CoreMask,
 }
+
+impl Region {
+	/// Returns the identifier of the region.
+	pub fn id(&self) -> RegionId {
+		RegionId { begin: self.begin, core: self.core, mask: self.mask }
+	}
+
+	/// Splits the region into two non-overlapping regions at the `pivot` timeslice.
+	///
+	/// This mirrors the `partition` extrinsic of pallet-broker: the first region keeps the
+	/// original identifier and ends at `pivot`, while the second region begins at `pivot` and
+	/// keeps the original end. The core and the mask stay the same for both regions.
+	pub fn partition(&self, pivot: Timeslice) -> Result<SplitRegions, RegionError> {
+		ensure!(pivot > self.begin, RegionError::PivotTooEarly);
+		ensure!(pivot < self.end, RegionError::PivotTooLate);
+
+		let first = Region { end: pivot, ..self.clone() };
+		let second = Region { begin: pivot, ..self.clone() };
+
+		Ok(((first.id(), first), (second.id(), second)))
+	}
+
+	/// Splits the region into two regions with complementary masks.
+	///
+	/// This mirrors the `interlace` extrinsic of pallet-broker: the first region gets
+	/// `pivot_mask` as its mask, while the second region gets the remaining bits of the original
+	/// mask. The `pivot_mask` must be a non-empty strict subset of the region's mask.
+	pub fn interlace(&self, pivot_mask: CoreMask) -> Result<SplitRegions, RegionError> {
+		ensure!(pivot_mask.is_subset_of(&self.mask), RegionError::ExteriorPivot);
+		ensure!(!pivot_mask.is_void(), RegionError::VoidPivot);
+		ensure!(pivot_mask != self.mask, RegionError::CompletePivot);
+
+		let first = Region { mask: pivot_mask, ..self.clone() };
+		let second = Region { mask: self.mask ^ pivot_mask, ..self.clone() };
+
+		Ok(((first.id(), first), (second.id(), second)))
+	}
+
+	/// Merges the region with `other` into a single region.
+	///
+	/// This is the inverse of `partition` and `interlace`: the regions must either be
+	/// time-adjacent with the same mask, or span the same period with disjoint masks. The order
+	/// of the two regions doesn't matter.
+	pub fn merge(&self, other: &Region) -> Result<Region, RegionError> {
+		ensure!(self.core == other.core, RegionError::CoreMismatch);
+
+		if self.mask == other.mask {
+			if self.end == other.begin {
+				return Ok(Region { end: other.end, ..self.clone() })
+			}
+			if other.end == self.begin {
+				return Ok(Region { begin: other.begin, ..self.clone() })
+			}
+		}
+
+		if self.begin == other.begin && self.end == other.end && self.mask.is_disjoint(&other.mask)
+		{
+			return Ok(Region { mask: self.mask | other.mask, ..self.clone() })
+		}
+
+		Err(RegionError::NotMergeable)
+	}
+}
+
+/// The record of a region as stored in the `Regions` map of pallet-broker on the Coretime chain.
+#[derive(scale::Decode, scale::Encode, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct RegionRecord {
+	/// The timeslice at which the region ends.
+	pub end: Timeslice,
+	/// The owner of the region.
+	pub owner: AccountId,
+	/// The amount paid to the broker for the region, if it was bought through a sale.
+	pub paid: Option<Balance>,
+}
+
+/// Whether the assignment of a region to a task can be changed afterwards.
+///
+/// This matches the `Finality` type of pallet-broker.
+#[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Finality {
+	/// The region remains with the same owner, allowing the assignment to be altered.
+	Provisional,
+	/// The region is removed; the assignment may be eligible for renewal.
+	Final,
+}
+
+/// The two regions resulting from splitting a region, each paired with its identifier.
+pub type SplitRegions = ((RegionId, Region), (RegionId, Region));
+
+/// Errors that can occur when splitting or merging regions.
+///
+/// The splitting errors match the ones returned by pallet-broker for `partition` and
+/// `interlace`. Since pallet-broker can't merge regions, `CoreMismatch` and `NotMergeable` have
+/// no equivalent there.
+#[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum RegionError {
+	/// The pivot point of the partition is at or before the beginning of the region.
+	PivotTooEarly,
+	/// The pivot point of the partition is at or after the end of the region.
+	PivotTooLate,
+	/// The pivot mask for the interlacing is not contained within the region's mask.
+	ExteriorPivot,
+	/// The pivot mask for the interlacing is void (and therefore unschedulable).
+	VoidPivot,
+	/// The pivot mask for the interlacing is complete (and therefore not a strict subset).
+	CompletePivot,
+	/// The regions to be merged belong to different cores.
+	CoreMismatch,
+	/// The regions to be merged are neither time-adjacent with the same mask, nor spanning the
+	/// same period with disjoint masks.
+	NotMergeable,
+}
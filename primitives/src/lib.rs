@@ -15,18 +15,63 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+pub mod assets;
+pub mod broker;
+pub mod calendar;
 pub mod coretime;
 pub mod macros;
+pub mod nfts;
+pub mod pallet_xcm;
+pub mod proof;
+#[cfg(feature = "serde")]
+pub mod serde_utils;
 pub mod uniques;
 
+#[cfg(test)]
+mod tests;
+
 /// Balance of an account.
 pub type Balance = u128;
 
 /// The type used for versioning metadata.
 pub type Version = u32;
 
-#[derive(scale::Encode, scale::Decode)]
+/// A call dispatched to the runtime through `call_runtime`, or to the runtime of the Coretime chain
+/// through XCM `Transact`.
+///
+/// Each variant carries the pallet and call indices with which it gets encoded, so the same
+/// contract code can be deployed to runtimes with different indices.
 pub enum RuntimeCall {
-	#[codec(index = 37)]
-	Uniques(uniques::UniquesCall),
+	Uniques(uniques::UniquesCallIndices, uniques::UniquesCall),
+	Nfts(nfts::NftsCallIndices, nfts::NftsCall),
+	Xcm(pallet_xcm::XcmCallIndices, pallet_xcm::XcmCall),
+	Broker(broker::BrokerCallIndices, broker::BrokerCall),
+	Assets(assets::AssetsCallIndices, assets::AssetsCall),
+}
+
+impl scale::Encode for RuntimeCall {
+	fn encode_to<T: scale::Output + ?Sized>(&self, output: &mut T) {
+		match self {
+			RuntimeCall::Uniques(indices, call) => {
+				output.push_byte(indices.pallet);
+				call.encode_to(indices, output);
+			},
+			RuntimeCall::Nfts(indices, call) => {
+				output.push_byte(indices.pallet);
+				call.encode_to(indices, output);
+			},
+			RuntimeCall::Xcm(indices, call) => {
+				output.push_byte(indices.pallet);
+				call.encode_to(indices, output);
+			},
+			RuntimeCall::Broker(indices, call) => {
+				output.push_byte(indices.pallet);
+				call.encode_to(indices, output);
+			},
+			RuntimeCall::Assets(indices, call) => {
+				output.push_byte(indices.pallet);
+				call.encode_to(indices, output);
+			},
+		}
+	}
 }
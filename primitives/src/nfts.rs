@@ -0,0 +1,168 @@
+// This file is part of RegionX.
+//
+// RegionX is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// RegionX is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with RegionX.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{coretime::RawRegionId, uniques::CollectionId, Balance};
+use ink::prelude::vec::Vec;
+use openbrush::traits::{AccountId, BlockNumber};
+use scale::{Encode, Output};
+use sp_runtime::MultiAddress;
+
+/// The indices of the nfts pallet and of its calls within the runtime.
+#[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NftsCallIndices {
+	/// The index of the nfts pallet in the runtime.
+	pub pallet: u8,
+	/// The index of the `transfer` call.
+	pub transfer: u8,
+	/// The index of the `approve_transfer` call.
+	pub approve_transfer: u8,
+	/// The index of the `cancel_approval` call.
+	pub cancel_approval: u8,
+}
+
+impl Default for NftsCallIndices {
+	/// The indices used in the Polkadot Asset Hub runtime.
+	fn default() -> Self {
+		Self { pallet: 52, transfer: 6, approve_transfer: 15, cancel_approval: 16 }
+	}
+}
+
+pub enum NftsCall {
+	Transfer {
+		collection: CollectionId,
+		item: RawRegionId,
+		dest: MultiAddress<AccountId, ()>,
+	},
+	ApproveTransfer {
+		collection: CollectionId,
+		item: RawRegionId,
+		delegate: MultiAddress<AccountId, ()>,
+		maybe_deadline: Option<BlockNumber>,
+	},
+	CancelApproval {
+		collection: CollectionId,
+		item: RawRegionId,
+		delegate: MultiAddress<AccountId, ()>,
+	},
+}
+
+impl NftsCall {
+	/// Encodes the call index followed by the arguments of the call.
+	pub fn encode_to<T: Output + ?Sized>(&self, indices: &NftsCallIndices, output: &mut T) {
+		match self {
+			NftsCall::Transfer { collection, item, dest } => {
+				output.push_byte(indices.transfer);
+				(collection, item, dest).encode_to(output);
+			},
+			NftsCall::ApproveTransfer { collection, item, delegate, maybe_deadline } => {
+				output.push_byte(indices.approve_transfer);
+				(collection, item, delegate, maybe_deadline).encode_to(output);
+			},
+			NftsCall::CancelApproval { collection, item, delegate } => {
+				output.push_byte(indices.cancel_approval);
+				(collection, item, delegate).encode_to(output);
+			},
+		}
+	}
+}
+
+#[derive(scale::Decode, scale::Encode, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+pub struct CollectionDetails {
+	/// Collection's owner.
+	pub owner: AccountId,
+	/// The total balance deposited by the owner for all the storage data associated with this
+	/// collection. Used by `destroy`.
+	pub owner_deposit: Balance,
+	/// The total number of outstanding items of this collection.
+	pub items: u32,
+	/// The total number of outstanding item metadata of this collection.
+	pub item_metadatas: u32,
+	/// The total number of outstanding item configs of this collection.
+	pub item_configs: u32,
+	/// The total number of attributes for this collection.
+	pub attributes: u32,
+}
+
+/// Information concerning the ownership of a single unique item.
+#[derive(scale::Decode, scale::Encode, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+pub struct ItemDetails {
+	/// The owner of this item.
+	pub owner: AccountId,
+	/// The approved transferrers of this item along with the optional deadline of each approval.
+	///
+	/// This is a `BoundedBTreeMap` in the pallet, which has the same encoding as a sorted vector
+	/// of key-value pairs.
+	pub approvals: Vec<(AccountId, Option<BlockNumber>)>,
+	/// The amount held in the pallet's default account for this item.
+	pub deposit: ItemDeposit,
+}
+
+impl ItemDetails {
+	/// Returns whether `delegate` is approved to transfer the item at block `now`.
+	///
+	/// Just like in pallet-nfts, an approval with a deadline expires once `now` is past the
+	/// deadline.
+	pub fn is_approved(&self, delegate: &AccountId, now: BlockNumber) -> bool {
+		self.approvals.iter().any(|(account, deadline)| {
+			account == delegate && deadline.map_or(true, |deadline| now <= deadline)
+		})
+	}
+}
+
+/// Information about the reserved item deposit.
+#[derive(scale::Decode, scale::Encode, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+pub struct ItemDeposit {
+	/// A depositor account.
+	pub account: AccountId,
+	/// An amount that gets reserved.
+	pub amount: Balance,
+}
+
+/// The settings of an item, encoded the same way as the `ItemSettings` bitflags of pallet-nfts.
+#[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ItemSettings(pub u64);
+
+impl ItemSettings {
+	/// Set if the item can be transferred.
+	pub const TRANSFERABLE: u64 = 1;
+
+	/// Returns whether the item is locked, i.e. whether it can't be transferred.
+	pub fn is_locked(&self) -> bool {
+		self.0 & Self::TRANSFERABLE == 0
+	}
+}
+
+/// The settings of a collection, encoded the same way as the `CollectionSettings` bitflags of
+/// pallet-nfts.
+#[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct CollectionSettings(pub u64);
+
+impl CollectionSettings {
+	/// Set if the items of the collection can be transferred.
+	pub const TRANSFERABLE_ITEMS: u64 = 1;
+
+	/// Returns whether the items of the collection are locked, i.e. whether they can't be
+	/// transferred.
+	pub fn is_locked(&self) -> bool {
+		self.0 & Self::TRANSFERABLE_ITEMS == 0
+	}
+}
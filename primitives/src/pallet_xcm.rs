@@ -0,0 +1,208 @@
+// This file is part of RegionX.
+//
+// RegionX is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// RegionX is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with RegionX.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{coretime::RawRegionId, Balance};
+use ink::prelude::{vec, vec::Vec};
+use openbrush::traits::AccountId;
+use scale::{Encode, Output};
+use xcm::{v3::prelude::*, VersionedMultiLocation, VersionedXcm};
+
+pub use sp_weights::Weight;
+
+/// The indices of pallet-xcm and of its calls within the runtime.
+#[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct XcmCallIndices {
+	/// The index of pallet-xcm in the runtime.
+	pub pallet: u8,
+	/// The index of the `send` call.
+	pub send: u8,
+	/// The index of the `execute` call.
+	pub execute: u8,
+}
+
+impl Default for XcmCallIndices {
+	/// The indices used in the Astar runtime.
+	fn default() -> Self {
+		Self { pallet: 51, send: 0, execute: 3 }
+	}
+}
+
+pub enum XcmCall {
+	Send { dest: VersionedMultiLocation, message: VersionedXcm<()> },
+	Execute { message: VersionedXcm<()>, max_weight: Weight },
+}
+
+impl XcmCall {
+	/// Encodes the call index followed by the arguments of the call.
+	pub fn encode_to<T: Output + ?Sized>(&self, indices: &XcmCallIndices, output: &mut T) {
+		match self {
+			XcmCall::Send { dest, message } => {
+				output.push_byte(indices.send);
+				(dest, message).encode_to(output);
+			},
+			XcmCall::Execute { message, max_weight } => {
+				output.push_byte(indices.execute);
+				(message, max_weight).encode_to(output);
+			},
+		}
+	}
+}
+
+/// Returns the location of `account` on the parachain `para_id`, relative to a sibling parachain.
+pub fn sibling_account(para_id: u32, account: &AccountId) -> MultiLocation {
+	MultiLocation::new(
+		1,
+		X2(
+			Parachain(para_id),
+			AccountId32 { network: None, id: *AsRef::<[u8; 32]>::as_ref(account) },
+		),
+	)
+}
+
+/// Builds the XCM which returns a region to the Coretime chain and deposits it to `beneficiary`.
+///
+/// The regions on this chain are reserve-backed by the Coretime chain, so the region is withdrawn
+/// locally and the Coretime chain, being its reserve, is instructed to release it. `fee` is the
+/// amount of relay chain tokens withdrawn along with the region for paying the execution on the
+/// Coretime chain. If it is zero, no fee asset is withdrawn and the execution isn't paid for, so
+/// the Coretime chain must allow it for free.
+///
+/// Returns `None` if the location of the region on the Coretime chain can't be derived from
+/// `reserve`.
+pub fn reserve_withdraw_region(
+	reserve: MultiLocation,
+	broker_pallet_index: u8,
+	region_id: RawRegionId,
+	fee: Balance,
+	beneficiary: &AccountId,
+) -> Option<Xcm<()>> {
+	let beneficiary = MultiLocation::new(
+		0,
+		X1(AccountId32 { network: None, id: *AsRef::<[u8; 32]>::as_ref(beneficiary) }),
+	);
+	reserve_withdraw_region_to(reserve, broker_pallet_index, region_id, fee, beneficiary)
+}
+
+/// Works like `reserve_withdraw_region`, except that the region is deposited to the
+/// `beneficiary` location, relative to the Coretime chain.
+pub fn reserve_withdraw_region_to(
+	reserve: MultiLocation,
+	broker_pallet_index: u8,
+	region_id: RawRegionId,
+	fee: Balance,
+	beneficiary: MultiLocation,
+) -> Option<Xcm<()>> {
+	let mut regions_location = reserve;
+	regions_location.push_interior(PalletInstance(broker_pallet_index)).ok()?;
+
+	let region = MultiAsset {
+		id: Concrete(regions_location),
+		fun: NonFungible(AssetInstance::Index(region_id)),
+	};
+
+	// Withdrawing a zero amount of tokens fails, so the fee asset is only included if needed.
+	let (assets, remote_xcm) = if fee == 0 {
+		(vec![region], vec![DepositAsset { assets: Wild(AllCounted(1)), beneficiary }])
+	} else {
+		let fee = MultiAsset { id: Concrete(MultiLocation::parent()), fun: Fungible(fee) };
+		(
+			vec![region, fee.clone()],
+			vec![
+				BuyExecution { fees: fee, weight_limit: Unlimited },
+				DepositAsset { assets: Wild(AllCounted(2)), beneficiary },
+			],
+		)
+	};
+	let count = assets.len() as u32;
+
+	Some(Xcm(vec![
+		WithdrawAsset(MultiAssets::from(assets)),
+		InitiateReserveWithdraw { assets: Wild(AllCounted(count)), reserve, xcm: Xcm(remote_xcm) },
+	]))
+}
+
+/// Builds the XCM which the Coretime chain executes for sending a region back to the sibling
+/// parachain `para_id`, where it gets deposited to `beneficiary`.
+///
+/// The Coretime chain is the reserve of the regions, so the region is withdrawn from the origin of
+/// the message and deposited to the sovereign account of `para_id`, which is instructed to mint
+/// its derivative. `fee` is the amount of relay chain tokens withdrawn along with the region for
+/// paying the execution on the sibling parachain. If it is zero, no fee asset is withdrawn and the
+/// execution isn't paid for, so the sibling parachain must allow it for free.
+pub fn reserve_transfer_region(
+	broker_pallet_index: u8,
+	region_id: RawRegionId,
+	fee: Balance,
+	para_id: u32,
+	beneficiary: &AccountId,
+) -> Xcm<()> {
+	let beneficiary = MultiLocation::new(
+		0,
+		X1(AccountId32 { network: None, id: *AsRef::<[u8; 32]>::as_ref(beneficiary) }),
+	);
+	let region = MultiAsset {
+		id: Concrete(MultiLocation::new(0, X1(PalletInstance(broker_pallet_index)))),
+		fun: NonFungible(AssetInstance::Index(region_id)),
+	};
+
+	// Withdrawing a zero amount of tokens fails, so the fee asset is only included if needed.
+	let (assets, remote_xcm) = if fee == 0 {
+		(vec![region], vec![DepositAsset { assets: Wild(AllCounted(1)), beneficiary }])
+	} else {
+		let fee = MultiAsset { id: Concrete(MultiLocation::parent()), fun: Fungible(fee) };
+		(
+			vec![region, fee.clone()],
+			vec![
+				BuyExecution { fees: fee, weight_limit: Unlimited },
+				DepositAsset { assets: Wild(AllCounted(2)), beneficiary },
+			],
+		)
+	};
+	let count = assets.len() as u32;
+
+	Xcm(vec![
+		WithdrawAsset(MultiAssets::from(assets)),
+		DepositReserveAsset {
+			assets: Wild(AllCounted(count)),
+			dest: MultiLocation::new(1, X1(Parachain(para_id))),
+			xcm: Xcm(remote_xcm),
+		},
+	])
+}
+
+/// Builds the XCM which dispatches the encoded `call` on the Coretime chain.
+///
+/// The call is dispatched from the account the Coretime chain derives from the origin of the
+/// message. `fee` is the amount of relay chain tokens withdrawn from that account for paying the
+/// execution, and `require_weight_at_most` the weight of the call.
+pub fn transact_on_coretime(
+	call: Vec<u8>,
+	fee: Balance,
+	require_weight_at_most: Weight,
+) -> Xcm<()> {
+	let fee = MultiAsset { id: Concrete(MultiLocation::parent()), fun: Fungible(fee) };
+
+	Xcm(vec![
+		WithdrawAsset(fee.clone().into()),
+		BuyExecution { fees: fee, weight_limit: Unlimited },
+		Transact {
+			origin_kind: OriginKind::SovereignAccount,
+			require_weight_at_most,
+			call: call.into(),
+		},
+	])
+}
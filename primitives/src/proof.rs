@@ -0,0 +1,135 @@
+// This file is part of RegionX.
+//
+// RegionX is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// RegionX is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with RegionX.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Verification of Coretime chain storage proofs.
+//!
+//! A storage proof is the set of trie nodes needed to walk from a state root down to a storage
+//! value. Given a trusted state root of the Coretime chain, this allows reading the pallet-broker
+//! record of a region without trusting the party which provided the proof.
+
+use crate::coretime::{RegionId, RegionRecord};
+use hash256_std_hasher::Hash256StdHasher;
+use ink::prelude::vec::Vec;
+use openbrush::traits::Hash;
+use scale::{DecodeAll, Encode};
+use sp_core::{Hasher, H256};
+use sp_core_hashing::{blake2_128, blake2_256, twox_128};
+use sp_trie::{read_trie_value, LayoutV1, StorageProof};
+
+/// The name under which pallet-broker is included in the Coretime chain runtime.
+pub const BROKER_PALLET_PREFIX: &[u8] = b"Broker";
+
+/// The name of the storage map which holds the region records in pallet-broker.
+pub const REGIONS_STORAGE_PREFIX: &[u8] = b"Regions";
+
+/// The hasher used by the state trie of the Coretime chain.
+///
+/// Unlike `sp_runtime::traits::BlakeTwo256`, this doesn't rely on host functions, which aren't
+/// available to contracts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Blake2Hasher;
+
+impl Hasher for Blake2Hasher {
+	type Out = H256;
+	type StdHasher = Hash256StdHasher;
+	const LENGTH: usize = 32;
+
+	fn hash(x: &[u8]) -> Self::Out {
+		blake2_256(x).into()
+	}
+}
+
+#[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum ProofError {
+	/// The proof doesn't contain all the trie nodes needed to read the region record.
+	IncompleteProof,
+	/// The proof shows that the region doesn't exist under the given state root.
+	RegionNotFound,
+	/// The value found under the region's storage key isn't a valid region record.
+	InvalidRecord,
+}
+
+/// Returns the storage key of the given region in the `Regions` map of pallet-broker.
+///
+/// The map is keyed with `Blake2_128Concat`, so the key is made of the hashed pallet and storage
+/// prefixes followed by the `blake2_128` hash of the encoded region id and the encoded id itself.
+pub fn region_storage_key(region_id: &RegionId) -> Vec<u8> {
+	let encoded_id = region_id.encode();
+
+	let mut key = Vec::with_capacity(32 + 16 + encoded_id.len());
+	key.extend_from_slice(&twox_128(BROKER_PALLET_PREFIX));
+	key.extend_from_slice(&twox_128(REGIONS_STORAGE_PREFIX));
+	key.extend_from_slice(&blake2_128(&encoded_id));
+	key.extend_from_slice(&encoded_id);
+
+	key
+}
+
+/// Reads the record of a region from a storage proof of the Coretime chain state.
+///
+/// `state_root` must come from a trusted source, since the proof only shows that the record is
+/// part of the state with that root.
+pub fn read_region_record(
+	state_root: &Hash,
+	region_id: &RegionId,
+	proof: Vec<Vec<u8>>,
+) -> Result<RegionRecord, ProofError> {
+	let root = H256::from_slice(state_root.as_ref());
+	let db = StorageProof::new(proof).into_memory_db::<Blake2Hasher>();
+
+	let value = read_trie_value::<LayoutV1<Blake2Hasher>, _>(
+		&db,
+		&root,
+		&region_storage_key(region_id),
+		None,
+		None,
+	)
+	.map_err(|_| ProofError::IncompleteProof)?
+	.ok_or(ProofError::RegionNotFound)?;
+
+	RegionRecord::decode_all(&mut &value[..]).map_err(|_| ProofError::InvalidRecord)
+}
+
+/// Builds a Coretime chain state holding the given `Regions` entries and returns its state root
+/// along with a storage proof of the entry of `proven`.
+///
+/// The values are taken as they are, so that malformed records can be put into the state as well.
+/// Meant for testing, since actual proofs should be retrieved from a Coretime chain node.
+#[cfg(feature = "std")]
+pub fn prove_region(entries: &[(RegionId, Vec<u8>)], proven: &RegionId) -> (Hash, Vec<Vec<u8>>) {
+	use sp_trie::{recorder::Recorder, MemoryDB, Trie, TrieDBBuilder, TrieDBMutBuilder, TrieMut};
+
+	let mut db = MemoryDB::<Blake2Hasher>::default();
+	let mut root = H256::default();
+	{
+		let mut trie = TrieDBMutBuilder::<LayoutV1<Blake2Hasher>>::new(&mut db, &mut root).build();
+		for (region_id, value) in entries {
+			trie.insert(&region_storage_key(region_id), value)
+				.expect("Failed to build the trie");
+		}
+	}
+
+	let recorder = Recorder::<Blake2Hasher>::default();
+	{
+		let mut trie_recorder = recorder.as_trie_recorder(root);
+		let trie = TrieDBBuilder::<LayoutV1<Blake2Hasher>>::new(&db, &root)
+			.with_recorder(&mut trie_recorder)
+			.build();
+		trie.get(&region_storage_key(proven)).expect("Failed to read the trie");
+	}
+
+	(Hash::from(root.0), recorder.drain_storage_proof().into_iter_nodes().collect())
+}
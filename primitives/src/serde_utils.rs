@@ -0,0 +1,148 @@
+// This file is part of RegionX.
+//
+// RegionX is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// RegionX is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with RegionX.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Helpers for serializing types which are not supported natively by JSON or which don't
+//! implement the `serde` traits.
+//!
+//! All the helpers produce strings:
+//! - `u128` values, like raw region ids and balances, are serialized as decimal strings since they
+//!   don't fit into a JSON number.
+//! - Account ids are serialized as `0x` followed by 64 hexadecimal digits.
+
+use core::{fmt, marker::PhantomData, str::FromStr};
+use openbrush::traits::AccountId;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+/// Serializes `u128` values, e.g. a `RawRegionId` or a `Balance`, as decimal strings.
+///
+/// Meant to be used as `#[serde(with = "primitives::serde_utils::u128_string")]`.
+pub mod u128_string {
+	use super::*;
+
+	pub fn serialize<S: Serializer>(value: &u128, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.collect_str(value)
+	}
+
+	pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u128, D::Error> {
+		deserialize_from_str(deserializer)
+	}
+}
+
+/// Serializes an `AccountId` as a `0x` prefixed hexadecimal string.
+///
+/// Meant to be used as `#[serde(with = "primitives::serde_utils::account_id")]`.
+pub mod account_id {
+	use super::*;
+
+	pub fn serialize<S: Serializer>(value: &AccountId, serializer: S) -> Result<S::Ok, S::Error> {
+		HexAccountId(*value).serialize(serializer)
+	}
+
+	pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<AccountId, D::Error> {
+		HexAccountId::deserialize(deserializer).map(|id| id.0)
+	}
+}
+
+/// Serializes an `Option<AccountId>` as either `null` or a `0x` prefixed hexadecimal string.
+///
+/// Meant to be used as `#[serde(with = "primitives::serde_utils::option_account_id")]`.
+pub mod option_account_id {
+	use super::*;
+
+	pub fn serialize<S: Serializer>(
+		value: &Option<AccountId>,
+		serializer: S,
+	) -> Result<S::Ok, S::Error> {
+		value.map(HexAccountId).serialize(serializer)
+	}
+
+	pub fn deserialize<'de, D: Deserializer<'de>>(
+		deserializer: D,
+	) -> Result<Option<AccountId>, D::Error> {
+		Option::<HexAccountId>::deserialize(deserializer).map(|id| id.map(|id| id.0))
+	}
+}
+
+/// Deserializes a value from a string through its `FromStr` implementation.
+pub(crate) fn deserialize_from_str<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+	T: FromStr,
+	T::Err: fmt::Display,
+	D: Deserializer<'de>,
+{
+	deserializer.deserialize_str(FromStrVisitor(PhantomData))
+}
+
+struct FromStrVisitor<T>(PhantomData<T>);
+
+impl<'de, T> de::Visitor<'de> for FromStrVisitor<T>
+where
+	T: FromStr,
+	T::Err: fmt::Display,
+{
+	type Value = T;
+
+	fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "a string")
+	}
+
+	fn visit_str<E: de::Error>(self, value: &str) -> Result<T, E> {
+		value.parse().map_err(E::custom)
+	}
+}
+
+struct HexAccountId(AccountId);
+
+impl fmt::Display for HexAccountId {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "0x")?;
+		AsRef::<[u8]>::as_ref(&self.0)
+			.iter()
+			.try_for_each(|byte| write!(f, "{:02x}", byte))
+	}
+}
+
+impl FromStr for HexAccountId {
+	type Err = &'static str;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		const ERROR: &str = "expected `0x` followed by 64 hex digits as account id";
+
+		let hex = s.strip_prefix("0x").ok_or(ERROR)?;
+		if hex.len() != 64 || !hex.bytes().all(|c| c.is_ascii_hexdigit()) {
+			return Err(ERROR)
+		}
+
+		let mut bytes = [0u8; 32];
+		for (byte, chunk) in bytes.iter_mut().zip(hex.as_bytes().chunks(2)) {
+			let chunk = core::str::from_utf8(chunk).map_err(|_| ERROR)?;
+			*byte = u8::from_str_radix(chunk, 16).map_err(|_| ERROR)?;
+		}
+
+		Ok(Self(AccountId::from(bytes)))
+	}
+}
+
+impl Serialize for HexAccountId {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.collect_str(self)
+	}
+}
+
+impl<'de> Deserialize<'de> for HexAccountId {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		deserialize_from_str(deserializer)
+	}
+}
@@ -0,0 +1,738 @@
+// This file is part of RegionX.
+//
+// RegionX is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// RegionX is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with RegionX.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+	assert_ok,
+	calendar::*,
+	coretime::{
+		CoreMask, ParseRegionError, RawRegionId, Region, RegionError, RegionId, RegionRecord,
+		Timeslice,
+	},
+	proof::*,
+};
+use openbrush::traits::{AccountId, BlockNumber};
+use scale::Encode;
+
+#[test]
+fn partition_works() {
+	let region = Region { begin: 2, end: 10, core: 1, mask: CoreMask::complete() };
+
+	// The pivot must be strictly within the region.
+	assert_eq!(region.partition(1), Err(RegionError::PivotTooEarly));
+	assert_eq!(region.partition(2), Err(RegionError::PivotTooEarly));
+	assert_eq!(region.partition(10), Err(RegionError::PivotTooLate));
+	assert_eq!(region.partition(11), Err(RegionError::PivotTooLate));
+
+	assert_ok!(
+		region.partition(4),
+		(
+			(
+				RegionId { begin: 2, core: 1, mask: CoreMask::complete() },
+				Region { begin: 2, end: 4, core: 1, mask: CoreMask::complete() }
+			),
+			(
+				RegionId { begin: 4, core: 1, mask: CoreMask::complete() },
+				Region { begin: 4, end: 10, core: 1, mask: CoreMask::complete() }
+			)
+		)
+	);
+}
+
+#[test]
+fn partitioned_region_ids_match_raw_ids() {
+	let region = Region { begin: 2, end: 10, core: 1, mask: CoreMask::from_chunk(0, 40) };
+	let ((first_id, first), (second_id, second)) = region.partition(6).unwrap();
+
+	// The first region keeps the identifier of the partitioned region.
+	assert_eq!(RawRegionId::from(first_id), RawRegionId::from(region.id()));
+	assert_eq!(RegionId::from(RawRegionId::from(first_id)), first.id());
+	assert_eq!(RegionId::from(RawRegionId::from(second_id)), second.id());
+}
+
+#[test]
+fn core_mask_algebra_works() {
+	let first_half = CoreMask::from_chunk(0, 40);
+	let second_half = CoreMask::from_chunk(40, 80);
+	let quarter = CoreMask::from_chunk(0, 20);
+
+	assert!(CoreMask::void().is_void());
+	assert!(CoreMask::complete().is_complete());
+	assert!(!first_half.is_void() && !first_half.is_complete());
+
+	assert_eq!(first_half | second_half, CoreMask::complete());
+	assert_eq!(first_half & second_half, CoreMask::void());
+	assert_eq!(first_half ^ quarter, CoreMask::from_chunk(20, 40));
+	assert_eq!(!first_half, second_half);
+	assert_eq!(!CoreMask::void(), CoreMask::complete());
+
+	let mut mask = quarter;
+	mask |= second_half;
+	mask &= !CoreMask::from_chunk(10, 20);
+	mask ^= CoreMask::from_chunk(70, 80);
+	assert_eq!(mask, CoreMask::from_chunk(0, 10) | CoreMask::from_chunk(40, 70));
+
+	assert!(quarter.is_subset_of(&first_half));
+	assert!(!first_half.is_subset_of(&quarter));
+	assert!(CoreMask::void().is_subset_of(&quarter));
+	assert!(first_half.is_disjoint(&second_half));
+	assert!(!first_half.is_disjoint(&quarter));
+
+	assert!(quarter.is_set(0) && quarter.is_set(19) && !quarter.is_set(20));
+	assert!(!CoreMask::complete().is_set(80));
+	assert_eq!(quarter.iter_ones().collect::<Vec<_>>(), (0..20).collect::<Vec<_>>());
+	assert_eq!(second_half.iter_zeros().collect::<Vec<_>>(), (0..40).collect::<Vec<_>>());
+	assert_eq!(CoreMask::complete().iter_ones().count(), 80);
+}
+
+#[test]
+fn interlace_works() {
+	let region = Region { begin: 2, end: 10, core: 1, mask: CoreMask::from_chunk(0, 40) };
+
+	// The pivot must be a non-empty strict subset of the region's mask.
+	assert_eq!(region.interlace(CoreMask::from_chunk(30, 50)), Err(RegionError::ExteriorPivot));
+	assert_eq!(region.interlace(CoreMask::complete()), Err(RegionError::ExteriorPivot));
+	assert_eq!(region.interlace(CoreMask::void()), Err(RegionError::VoidPivot));
+	assert_eq!(region.interlace(CoreMask::from_chunk(0, 40)), Err(RegionError::CompletePivot));
+
+	assert_ok!(
+		region.interlace(CoreMask::from_chunk(0, 10)),
+		(
+			(
+				RegionId { begin: 2, core: 1, mask: CoreMask::from_chunk(0, 10) },
+				Region { begin: 2, end: 10, core: 1, mask: CoreMask::from_chunk(0, 10) }
+			),
+			(
+				RegionId { begin: 2, core: 1, mask: CoreMask::from_chunk(10, 40) },
+				Region { begin: 2, end: 10, core: 1, mask: CoreMask::from_chunk(10, 40) }
+			)
+		)
+	);
+}
+
+#[test]
+fn merge_works() {
+	let region = Region { begin: 2, end: 10, core: 1, mask: CoreMask::from_chunk(0, 40) };
+
+	// Merging the results of a split gives back the original region, in any order.
+	let ((_, first), (_, second)) = region.partition(6).unwrap();
+	assert_ok!(first.merge(&second), region.clone());
+	assert_ok!(second.merge(&first), region.clone());
+
+	let ((_, first), (_, second)) = region.interlace(CoreMask::from_chunk(0, 10)).unwrap();
+	assert_ok!(first.merge(&second), region.clone());
+	assert_ok!(second.merge(&first), region.clone());
+
+	// The regions must belong to the same core.
+	assert_eq!(
+		region.merge(&Region { core: 2, begin: 10, ..region.clone() }),
+		Err(RegionError::CoreMismatch)
+	);
+
+	// Time-adjacent regions must have the same mask.
+	assert_eq!(
+		region.merge(&Region { begin: 10, end: 12, mask: CoreMask::complete(), ..region.clone() }),
+		Err(RegionError::NotMergeable)
+	);
+	// Regions which aren't time-adjacent can't be merged.
+	assert_eq!(
+		region.merge(&Region { begin: 11, end: 12, ..region.clone() }),
+		Err(RegionError::NotMergeable)
+	);
+	// Regions spanning the same period must have disjoint masks.
+	assert_eq!(
+		region.merge(&Region { mask: CoreMask::from_chunk(30, 50), ..region.clone() }),
+		Err(RegionError::NotMergeable)
+	);
+	assert_eq!(
+		region.merge(&Region { end: 12, mask: CoreMask::from_chunk(40, 50), ..region.clone() }),
+		Err(RegionError::NotMergeable)
+	);
+}
+
+#[test]
+fn timeslice_conversions_work() {
+	let config = BrokerConfig::default();
+
+	assert_eq!(block_to_timeslice(0, TIMESLICE_PERIOD), 0);
+	assert_eq!(block_to_timeslice(79, TIMESLICE_PERIOD), 0);
+	assert_eq!(block_to_timeslice(80, TIMESLICE_PERIOD), 1);
+	assert_eq!(timeslice_to_block(3, TIMESLICE_PERIOD), 240);
+
+	assert_eq!(config.timeslice_at(config.timeslice_start(42)), 42);
+	assert_eq!(config.timeslice_at(config.timeslice_start(42) - 1), 41);
+
+	// A zero timeslice period doesn't panic.
+	assert_eq!(block_to_timeslice(80, 0), 0);
+	assert_eq!(timeslice_to_block(Timeslice::MAX, TIMESLICE_PERIOD), BlockNumber::MAX);
+}
+
+#[test]
+fn bulk_period_works() {
+	let config = BrokerConfig { region_length: 10, ..Default::default() };
+
+	assert_eq!(config.bulk_period(100, 100), Some(BulkPeriod { begin: 100, end: 110 }));
+	assert_eq!(config.bulk_period(100, 109), Some(BulkPeriod { begin: 100, end: 110 }));
+	assert_eq!(config.bulk_period(100, 125), Some(BulkPeriod { begin: 120, end: 130 }));
+	assert_eq!(config.bulk_period(100, 99), Some(BulkPeriod { begin: 90, end: 100 }));
+	assert_eq!(config.bulk_period(100, 90), Some(BulkPeriod { begin: 90, end: 100 }));
+	assert_eq!(config.bulk_period(100, 5), Some(BulkPeriod { begin: 0, end: 10 }));
+
+	// Periods before timeslice zero don't line up with the grid, so there are none:
+	assert_eq!(config.bulk_period(15, 14), Some(BulkPeriod { begin: 5, end: 15 }));
+	assert_eq!(config.bulk_period(15, 4), None);
+	assert_eq!(config.bulk_period(5, 0), None);
+	assert_eq!(config.bulk_period(5, 5), Some(BulkPeriod { begin: 5, end: 15 }));
+	// Periods ending beyond the last timeslice don't exist either:
+	assert_eq!(config.bulk_period(0, Timeslice::MAX), None);
+}
+
+#[test]
+fn sale_phase_works() {
+	let config = BrokerConfig { interlude_length: 20, leadin_length: 10, ..Default::default() };
+
+	assert_eq!(config.interlude_start(100), 80);
+	// Blocks before the interlude belong to the previous sale:
+	assert_eq!(config.sale_phase(100, 0), None);
+	assert_eq!(config.sale_phase(100, 79), None);
+	assert_eq!(config.sale_phase(100, 80), Some(SalePhase::Interlude));
+	assert_eq!(config.sale_phase(100, 99), Some(SalePhase::Interlude));
+	assert_eq!(config.sale_phase(100, 100), Some(SalePhase::Leadin));
+	assert_eq!(config.sale_phase(100, 109), Some(SalePhase::Leadin));
+	assert_eq!(config.sale_phase(100, 110), Some(SalePhase::FixedPrice));
+
+	// The interlude can't start before block zero:
+	assert_eq!(config.interlude_start(10), 0);
+	assert_eq!(config.sale_phase(10, 0), Some(SalePhase::Interlude));
+}
+
+#[test]
+fn timestamp_estimation_works() {
+	let config = BrokerConfig::default();
+	let now_timestamp = 1_000_000;
+
+	assert_eq!(config.estimate_timestamp(100, now_timestamp, 100), now_timestamp);
+	assert_eq!(config.estimate_timestamp(100, now_timestamp, 110), now_timestamp + 60_000);
+	assert_eq!(config.estimate_timestamp(100, now_timestamp, 90), now_timestamp - 60_000);
+	assert_eq!(config.estimate_timestamp(1_000, now_timestamp, 0), 0);
+
+	// Timeslice 2 starts at block 160.
+	assert_eq!(
+		config.estimate_timeslice_timestamp(100, now_timestamp, 2),
+		now_timestamp + 60 * RELAY_BLOCK_TIME
+	);
+}
+
+#[test]
+fn core_mask_textual_format_works() {
+	let mask = CoreMask::from_chunk(0, 20);
+
+	let bits = mask.to_string();
+	assert_eq!(bits, format!("{}{}", "1".repeat(20), "0".repeat(60)));
+	assert_eq!(format!("{:x}", mask), "fffff000000000000000");
+	assert_eq!(format!("{:#x}", mask), "0xfffff000000000000000");
+
+	// Both forms round-trip.
+	assert_eq!(bits.parse::<CoreMask>(), Ok(mask));
+	assert_eq!(format!("{:#x}", mask).parse::<CoreMask>(), Ok(mask));
+	assert_eq!("0xFFFFF000000000000000".parse::<CoreMask>(), Ok(mask));
+
+	assert_eq!("".parse::<CoreMask>(), Err(ParseRegionError::InvalidMask));
+	assert_eq!("1".repeat(79).parse::<CoreMask>(), Err(ParseRegionError::InvalidMask));
+	assert_eq!("2".repeat(80).parse::<CoreMask>(), Err(ParseRegionError::InvalidMask));
+	assert_eq!("0xfffff".parse::<CoreMask>(), Err(ParseRegionError::InvalidMask));
+	assert_eq!("0x+ffff000000000000000".parse::<CoreMask>(), Err(ParseRegionError::InvalidMask));
+}
+
+#[test]
+fn region_id_textual_format_works() {
+	let id = RegionId { begin: 4, core: 2, mask: CoreMask::from_chunk(0, 40) };
+	let raw_id = RawRegionId::from(id);
+
+	assert_eq!(id.to_string(), "4:2:0xffffffffff0000000000");
+	assert_eq!(format!("{:#x}", id), format!("{:#034x}", raw_id));
+	assert_eq!(format!("{:x}", id), "000000040002ffffffffff0000000000");
+
+	// All forms round-trip.
+	assert_eq!(id.to_string().parse::<RegionId>(), Ok(id));
+	assert_eq!(format!("{:#x}", id).parse::<RegionId>(), Ok(id));
+	assert_eq!(format!("4:2:{}", id.mask).parse::<RegionId>(), Ok(id));
+	assert_eq!(
+		format!("{:#x}", RegionId::from(u128::MAX)).parse::<RegionId>(),
+		Ok(RegionId::from(u128::MAX))
+	);
+
+	assert_eq!("4:2".parse::<RegionId>(), Err(ParseRegionError::InvalidFormat));
+	assert_eq!("4:2:0x00:1".parse::<RegionId>(), Err(ParseRegionError::InvalidFormat));
+	assert_eq!(
+		"x:2:0xffffffffff0000000000".parse::<RegionId>(),
+		Err(ParseRegionError::InvalidBegin)
+	);
+	assert_eq!(
+		"4:70000:0xffffffffff0000000000".parse::<RegionId>(),
+		Err(ParseRegionError::InvalidCore)
+	);
+	assert_eq!("4:2:0xff".parse::<RegionId>(), Err(ParseRegionError::InvalidMask));
+	assert_eq!("0x0400".parse::<RegionId>(), Err(ParseRegionError::InvalidRawId));
+
+	assert_eq!(
+		ParseRegionError::InvalidRawId.to_string(),
+		"expected `0x` followed by 32 hex digits as raw region id"
+	);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_json_shapes_are_stable() {
+	use crate::uniques::ItemDetails;
+	use openbrush::traits::AccountId;
+	use serde_json::json;
+
+	let region = Region { begin: 4, end: 8, core: 2, mask: CoreMask::from_chunk(0, 40) };
+	let region_json = json!({ "begin": 4, "end": 8, "core": 2, "mask": "0xffffffffff0000000000" });
+	assert_eq!(serde_json::to_value(&region).unwrap(), region_json);
+	assert_eq!(serde_json::from_value::<Region>(region_json).unwrap(), region);
+
+	let id = region.id();
+	let id_json = json!({ "begin": 4, "core": 2, "mask": "0xffffffffff0000000000" });
+	assert_eq!(serde_json::to_value(id).unwrap(), id_json);
+	assert_eq!(serde_json::from_value::<RegionId>(id_json).unwrap(), id);
+
+	let item = ItemDetails {
+		owner: AccountId::from([1u8; 32]),
+		approved: None,
+		is_frozen: false,
+		deposit: u128::MAX,
+	};
+	let item_json = json!({
+		"owner": format!("0x{}", "01".repeat(32)),
+		"approved": null,
+		"is_frozen": false,
+		"deposit": "340282366920938463463374607431768211455",
+	});
+	assert_eq!(serde_json::to_value(&item).unwrap(), item_json);
+	assert_eq!(serde_json::from_value::<ItemDetails>(item_json).unwrap(), item);
+
+	let approved = ItemDetails { approved: Some(AccountId::from([255u8; 32])), ..item };
+	let approved_json = serde_json::to_value(&approved).unwrap();
+	assert_eq!(approved_json["approved"], json!(format!("0x{}", "ff".repeat(32))));
+	assert_eq!(serde_json::from_value::<ItemDetails>(approved_json).unwrap(), approved);
+
+	// Invalid values are rejected.
+	assert!(serde_json::from_value::<CoreMask>(json!("0xff")).is_err());
+	assert!(serde_json::from_value::<CoreMask>(json!(255)).is_err());
+	assert!(serde_json::from_str::<ItemDetails>(
+		r#"{"owner":"0x01","approved":null,"is_frozen":false,"deposit":"0"}"#
+	)
+	.is_err());
+}
+
+#[test]
+fn runtime_call_encoding_uses_configured_indices() {
+	use crate::{
+		uniques::{UniquesCall, UniquesCallIndices},
+		RuntimeCall,
+	};
+	use openbrush::traits::AccountId;
+	use scale::Encode;
+
+	let dest = AccountId::from([1u8; 32]);
+	let args = (42u32, 7u128, sp_runtime::MultiAddress::<AccountId, ()>::Id(dest)).encode();
+	let transfer = || UniquesCall::Transfer { collection: 42, item: 7, dest: dest.into() };
+
+	// The default indices match the ones previously hardcoded for Astar.
+	let call = RuntimeCall::Uniques(Default::default(), transfer());
+	assert_eq!(call.encode(), [&[37u8, 5][..], &args].concat());
+
+	let indices =
+		UniquesCallIndices { pallet: 12, transfer: 1, approve_transfer: 2, cancel_approval: 3 };
+	let call = RuntimeCall::Uniques(indices, transfer());
+	assert_eq!(call.encode(), [&[12u8, 1][..], &args].concat());
+
+	let call = RuntimeCall::Uniques(
+		indices,
+		UniquesCall::CancelApproval { collection: 42, item: 7, maybe_check_delegate: None },
+	);
+	assert_eq!(call.encode(), [&[12u8, 3][..], &(42u32, 7u128, 0u8).encode()].concat());
+}
+
+#[test]
+fn nfts_call_encoding_works() {
+	use crate::{
+		nfts::{NftsCall, NftsCallIndices},
+		RuntimeCall,
+	};
+	use openbrush::traits::AccountId;
+	use scale::Encode;
+
+	let delegate = AccountId::from([1u8; 32]);
+	let address = sp_runtime::MultiAddress::<AccountId, ()>::Id(delegate);
+
+	let call = RuntimeCall::Nfts(
+		NftsCallIndices::default(),
+		NftsCall::Transfer { collection: 42, item: 7, dest: delegate.into() },
+	);
+	assert_eq!(call.encode(), [&[52u8, 6][..], &(42u32, 7u128, &address).encode()].concat());
+
+	let call = RuntimeCall::Nfts(
+		NftsCallIndices::default(),
+		NftsCall::ApproveTransfer {
+			collection: 42,
+			item: 7,
+			delegate: delegate.into(),
+			maybe_deadline: Some(100),
+		},
+	);
+	assert_eq!(
+		call.encode(),
+		[&[52u8, 15][..], &(42u32, 7u128, &address, Some(100u32)).encode()].concat()
+	);
+}
+
+#[test]
+fn nfts_approval_deadline_is_respected() {
+	use crate::nfts::{ItemDeposit, ItemDetails};
+
+	let owner = AccountId::from([0u8; 32]);
+	let delegate = AccountId::from([1u8; 32]);
+	let other = AccountId::from([2u8; 32]);
+
+	let item = ItemDetails {
+		owner,
+		approvals: vec![(delegate, Some(10)), (other, None)],
+		deposit: ItemDeposit { account: owner, amount: 0 },
+	};
+
+	assert!(item.is_approved(&delegate, 10));
+	// The approval expires once the deadline is passed.
+	assert!(!item.is_approved(&delegate, 11));
+	// Approvals without a deadline never expire.
+	assert!(item.is_approved(&other, BlockNumber::MAX));
+	assert!(!item.is_approved(&owner, 0));
+}
+
+#[test]
+fn nfts_settings_work() {
+	use crate::nfts::{CollectionSettings, ItemSettings};
+	use scale::Decode;
+
+	// Items are transferable unless the `Transferable` flag is cleared.
+	assert!(!ItemSettings(ItemSettings::TRANSFERABLE).is_locked());
+	assert!(!ItemSettings(0b111).is_locked());
+	assert!(ItemSettings(0b110).is_locked());
+
+	assert!(!CollectionSettings(CollectionSettings::TRANSFERABLE_ITEMS).is_locked());
+	assert!(CollectionSettings(0b11110).is_locked());
+
+	// The settings are encoded as the underlying bitflags.
+	assert_eq!(ItemSettings(1).encode(), 1u64.encode());
+	assert_eq!(CollectionSettings::decode(&mut &16u64.encode()[..]), Ok(CollectionSettings(16)));
+}
+
+#[test]
+fn xcm_call_encoding_works() {
+	use crate::{
+		pallet_xcm::{
+			reserve_transfer_region, reserve_withdraw_region, reserve_withdraw_region_to,
+			sibling_account, Weight, XcmCall, XcmCallIndices,
+		},
+		RuntimeCall,
+	};
+	use openbrush::traits::AccountId;
+	use scale::Encode;
+	use xcm::{v3::prelude::*, VersionedXcm};
+
+	let beneficiary = AccountId::from([1u8; 32]);
+	let coretime_chain = MultiLocation::new(1, X1(Parachain(1005)));
+
+	let message = reserve_withdraw_region(coretime_chain, 50, 7, 1_000, &beneficiary).unwrap();
+	let region = MultiAsset {
+		id: Concrete(MultiLocation::new(1, X2(Parachain(1005), PalletInstance(50)))),
+		fun: NonFungible(AssetInstance::Index(7)),
+	};
+	let fee = MultiAsset { id: Concrete(MultiLocation::parent()), fun: Fungible(1_000) };
+	assert_eq!(
+		message,
+		Xcm(vec![
+			WithdrawAsset(vec![region, fee.clone()].into()),
+			InitiateReserveWithdraw {
+				assets: Wild(AllCounted(2)),
+				reserve: coretime_chain,
+				xcm: Xcm(vec![
+					BuyExecution { fees: fee.clone(), weight_limit: Unlimited },
+					DepositAsset {
+						assets: Wild(AllCounted(2)),
+						beneficiary: MultiLocation::new(
+							0,
+							X1(AccountId32 { network: None, id: [1u8; 32] })
+						),
+					},
+				]),
+			},
+		])
+	);
+
+	// The location of the regions can't be derived from a location without free junctions.
+	let too_long = MultiLocation::new(
+		1,
+		X8(
+			Parachain(1),
+			Parachain(2),
+			Parachain(3),
+			Parachain(4),
+			Parachain(5),
+			Parachain(6),
+			Parachain(7),
+			Parachain(8),
+		),
+	);
+	assert_eq!(reserve_withdraw_region(too_long, 50, 7, 1_000, &beneficiary), None);
+
+	// The region can be deposited to an account on another parachain.
+	let contract = AccountId::from([2u8; 32]);
+	let contract_location =
+		MultiLocation::new(1, X2(Parachain(2000), AccountId32 { network: None, id: [2u8; 32] }));
+	assert_eq!(sibling_account(2000, &contract), contract_location);
+	let message = reserve_withdraw_region_to(coretime_chain, 50, 7, 1_000, contract_location);
+	assert_eq!(
+		message.map(|message| message.0[1].clone()),
+		Some(InitiateReserveWithdraw {
+			assets: Wild(AllCounted(2)),
+			reserve: coretime_chain,
+			xcm: Xcm(vec![
+				BuyExecution { fees: fee.clone(), weight_limit: Unlimited },
+				DepositAsset { assets: Wild(AllCounted(2)), beneficiary: contract_location },
+			]),
+		})
+	);
+
+	// No fee asset is withdrawn if the fee is zero.
+	let region = MultiAsset {
+		id: Concrete(MultiLocation::new(1, X2(Parachain(1005), PalletInstance(50)))),
+		fun: NonFungible(AssetInstance::Index(7)),
+	};
+	assert_eq!(
+		reserve_withdraw_region(coretime_chain, 50, 7, 0, &beneficiary),
+		Some(Xcm(vec![
+			WithdrawAsset(region.into()),
+			InitiateReserveWithdraw {
+				assets: Wild(AllCounted(1)),
+				reserve: coretime_chain,
+				xcm: Xcm(vec![DepositAsset {
+					assets: Wild(AllCounted(1)),
+					beneficiary: MultiLocation::new(
+						0,
+						X1(AccountId32 { network: None, id: [1u8; 32] })
+					),
+				}]),
+			},
+		]))
+	);
+
+	// The Coretime chain sends the region back to the sibling parachain as its reserve.
+	let region = MultiAsset {
+		id: Concrete(MultiLocation::new(0, X1(PalletInstance(50)))),
+		fun: NonFungible(AssetInstance::Index(7)),
+	};
+	assert_eq!(
+		reserve_transfer_region(50, 7, 1_000, 2000, &contract),
+		Xcm(vec![
+			WithdrawAsset(vec![region.clone(), fee.clone()].into()),
+			DepositReserveAsset {
+				assets: Wild(AllCounted(2)),
+				dest: MultiLocation::new(1, X1(Parachain(2000))),
+				xcm: Xcm(vec![
+					BuyExecution { fees: fee.clone(), weight_limit: Unlimited },
+					DepositAsset {
+						assets: Wild(AllCounted(2)),
+						beneficiary: MultiLocation::new(
+							0,
+							X1(AccountId32 { network: None, id: [2u8; 32] })
+						),
+					},
+				]),
+			},
+		])
+	);
+	assert_eq!(
+		reserve_transfer_region(50, 7, 0, 2000, &contract).0[0],
+		WithdrawAsset(region.into())
+	);
+
+	let max_weight = Weight::from_parts(1_000_000_000, 100_000);
+	let args = (VersionedXcm::V3(message.clone()), max_weight).encode();
+	let execute = || XcmCall::Execute { message: VersionedXcm::V3(message.clone()), max_weight };
+
+	// The default indices match the ones used in the Astar runtime.
+	let call = RuntimeCall::Xcm(Default::default(), execute());
+	assert_eq!(call.encode(), [&[51u8, 3][..], &args].concat());
+
+	let call = RuntimeCall::Xcm(XcmCallIndices { pallet: 31, send: 0, execute: 1 }, execute());
+	assert_eq!(call.encode(), [&[31u8, 1][..], &args].concat());
+}
+
+#[test]
+fn broker_call_encoding_works() {
+	use crate::{
+		broker::{BrokerCall, BrokerCallIndices},
+		coretime::Finality,
+		pallet_xcm::{transact_on_coretime, Weight, XcmCall},
+		RuntimeCall,
+	};
+	use scale::Encode;
+	use xcm::{v3::prelude::*, VersionedMultiLocation, VersionedXcm};
+
+	let region_id = RegionId { begin: 2, core: 1, mask: CoreMask::complete() };
+	let assign = |finality| BrokerCall::Assign { region_id, task: 2000, finality };
+
+	// The finality is encoded like its pallet-broker counterpart.
+	assert_eq!(Finality::Provisional.encode(), vec![0]);
+	assert_eq!(Finality::Final.encode(), vec![1]);
+
+	// The default indices match the ones used in the Kusama Coretime chain runtime.
+	let call = RuntimeCall::Broker(Default::default(), assign(Finality::Final)).encode();
+	assert_eq!(call, [&[50u8, 10][..], &(region_id, 2000u32, 1u8).encode()].concat());
+
+	let indices = BrokerCallIndices { pallet: 22, assign: 3, pool: 4, claim_revenue: 5 };
+	let call = RuntimeCall::Broker(indices, assign(Finality::Provisional)).encode();
+	assert_eq!(call, [&[22u8, 3][..], &(region_id, 2000u32, 0u8).encode()].concat());
+
+	let payee = AccountId::from([1u8; 32]);
+	let pool = BrokerCall::Pool { region_id, payee, finality: Finality::Final };
+	assert_eq!(
+		RuntimeCall::Broker(indices, pool).encode(),
+		[&[22u8, 4][..], &(region_id, payee, 1u8).encode()].concat()
+	);
+
+	let claim_revenue = BrokerCall::ClaimRevenue { region_id, max_timeslices: 5 };
+	assert_eq!(
+		RuntimeCall::Broker(indices, claim_revenue).encode(),
+		[&[22u8, 5][..], &(region_id, 5u32).encode()].concat()
+	);
+
+	// The call is dispatched on the Coretime chain after paying for the execution.
+	let weight = Weight::from_parts(1_000_000_000, 100_000);
+	let message = transact_on_coretime(call.clone(), 1_000, weight);
+	let fee = MultiAsset { id: Concrete(MultiLocation::parent()), fun: Fungible(1_000) };
+	assert_eq!(
+		message,
+		Xcm(vec![
+			WithdrawAsset(fee.clone().into()),
+			BuyExecution { fees: fee, weight_limit: Unlimited },
+			Transact {
+				origin_kind: OriginKind::SovereignAccount,
+				require_weight_at_most: weight,
+				call: call.into()
+			},
+		])
+	);
+
+	let dest = VersionedMultiLocation::V3(MultiLocation::new(1, X1(Parachain(1005))));
+	let send = RuntimeCall::Xcm(
+		Default::default(),
+		XcmCall::Send { dest: dest.clone(), message: VersionedXcm::V3(message.clone()) },
+	);
+	assert_eq!(
+		send.encode(),
+		[&[51u8, 0][..], &(dest, VersionedXcm::V3(message)).encode()].concat()
+	);
+}
+
+#[test]
+fn region_storage_key_works() {
+	let region_id = RegionId { begin: 2, core: 1, mask: CoreMask::complete() };
+	let key = region_storage_key(&region_id);
+
+	// twox_128("Broker") ++ twox_128("Regions") ++ blake2_128(id) ++ id
+	assert_eq!(key.len(), 16 + 16 + 16 + 16);
+	assert_eq!(key[..32], region_storage_key(&Default::default())[..32]);
+	assert_eq!(key[48..], region_id.encode()[..]);
+}
+
+#[test]
+fn read_region_record_works() {
+	let region_id = RegionId { begin: 2, core: 1, mask: CoreMask::complete() };
+	let record = RegionRecord { end: 10, owner: AccountId::from([1; 32]), paid: Some(100) };
+	let other_id = RegionId { begin: 2, core: 2, mask: CoreMask::complete() };
+	let other_record = RegionRecord { end: 12, owner: AccountId::from([2; 32]), paid: None };
+	let entries = vec![
+		(region_id, record.encode()),
+		(other_id, other_record.encode()),
+		(Default::default(), vec![1, 2, 3]),
+	];
+
+	let (root, proof) = prove_region(&entries, &region_id);
+	assert_ok!(read_region_record(&root, &region_id, proof.clone()), record);
+
+	// The proof only covers the nodes leading to `region_id`.
+	assert_eq!(
+		read_region_record(&root, &other_id, proof.clone()),
+		Err(ProofError::IncompleteProof)
+	);
+	// The proof is worthless under a different state root.
+	assert_eq!(
+		read_region_record(&Default::default(), &region_id, proof),
+		Err(ProofError::IncompleteProof)
+	);
+
+	// Proving that a region doesn't exist:
+	let missing_id = RegionId { begin: 3, core: 1, mask: CoreMask::complete() };
+	let (root, proof) = prove_region(&entries, &missing_id);
+	assert_eq!(read_region_record(&root, &missing_id, proof), Err(ProofError::RegionNotFound));
+
+	// The value under the region's key must be a valid record:
+	let (root, proof) = prove_region(&entries, &Default::default());
+	assert_eq!(
+		read_region_record(&root, &Default::default(), proof),
+		Err(ProofError::InvalidRecord)
+	);
+}
+
+#[test]
+fn assets_call_encoding_works() {
+	use crate::{
+		assets::{AssetsCall, AssetsCallIndices},
+		RuntimeCall,
+	};
+	use openbrush::traits::AccountId;
+	use scale::{Compact, Encode};
+	use sp_runtime::MultiAddress;
+
+	let (owner, destination) = (AccountId::from([1u8; 32]), AccountId::from([2u8; 32]));
+	let transfer_approved = || AssetsCall::TransferApproved {
+		id: 340282366920938463463374607431768211455,
+		owner: owner.into(),
+		destination: destination.into(),
+		amount: 1_000,
+	};
+	let args = (
+		Compact(340282366920938463463374607431768211455u128),
+		MultiAddress::<AccountId, ()>::Id(owner),
+		MultiAddress::<AccountId, ()>::Id(destination),
+		Compact(1_000u128),
+	)
+		.encode();
+
+	// The default indices match the ones used in the Astar runtime.
+	let call = RuntimeCall::Assets(Default::default(), transfer_approved());
+	assert_eq!(call.encode(), [&[36u8, 25][..], &args].concat());
+
+	let indices = AssetsCallIndices { pallet: 12, transfer_approved: 3 };
+	let call = RuntimeCall::Assets(indices, transfer_approved());
+	assert_eq!(call.encode(), [&[12u8, 3][..], &args].concat());
+
+	// The amount is compact encoded, just like in the pallet.
+	let encoded = call.encode();
+	assert_eq!(encoded[encoded.len() - 2..], [0xa1, 0x0f]);
+}
@@ -15,22 +15,48 @@
 
 use crate::{coretime::RawRegionId, Balance};
 use openbrush::traits::AccountId;
+use scale::{Encode, Output};
 use sp_runtime::MultiAddress;
 
 // The type used to identify collections in the underlying uniques pallet.
 pub type CollectionId = u32;
 
-#[derive(scale::Encode, scale::Decode)]
+/// The indices of the uniques pallet and of its calls within the runtime.
+///
+/// These vary across chains, so they are configured when instantiating the contract rather than
+/// being hardcoded.
+#[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UniquesCallIndices {
+	/// The index of the uniques pallet in the runtime.
+	pub pallet: u8,
+	/// The index of the `transfer` call.
+	pub transfer: u8,
+	/// The index of the `approve_transfer` call.
+	pub approve_transfer: u8,
+	/// The index of the `cancel_approval` call.
+	pub cancel_approval: u8,
+}
+
+impl Default for UniquesCallIndices {
+	/// The indices used in the Astar runtime.
+	fn default() -> Self {
+		Self { pallet: 37, transfer: 5, approve_transfer: 13, cancel_approval: 14 }
+	}
+}
+
 pub enum UniquesCall {
-	#[codec(index = 5)]
-	Transfer { collection: CollectionId, item: RawRegionId, dest: MultiAddress<AccountId, ()> },
-	#[codec(index = 13)]
+	Transfer {
+		collection: CollectionId,
+		item: RawRegionId,
+		dest: MultiAddress<AccountId, ()>,
+	},
 	ApproveTransfer {
 		collection: CollectionId,
 		item: RawRegionId,
 		delegate: MultiAddress<AccountId, ()>,
 	},
-	#[codec(index = 14)]
 	CancelApproval {
 		collection: CollectionId,
 		item: RawRegionId,
@@ -38,19 +64,45 @@ pub enum UniquesCall {
 	},
 }
 
+impl UniquesCall {
+	/// Encodes the call index followed by the arguments of the call.
+	pub fn encode_to<T: Output + ?Sized>(&self, indices: &UniquesCallIndices, output: &mut T) {
+		match self {
+			UniquesCall::Transfer { collection, item, dest } => {
+				output.push_byte(indices.transfer);
+				(collection, item, dest).encode_to(output);
+			},
+			UniquesCall::ApproveTransfer { collection, item, delegate } => {
+				output.push_byte(indices.approve_transfer);
+				(collection, item, delegate).encode_to(output);
+			},
+			UniquesCall::CancelApproval { collection, item, maybe_check_delegate } => {
+				output.push_byte(indices.cancel_approval);
+				(collection, item, maybe_check_delegate).encode_to(output);
+			},
+		}
+	}
+}
+
 #[derive(scale::Decode, scale::Encode, Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CollectionDetails {
 	/// Can change `owner`, `issuer`, `freezer` and `admin` accounts.
+	#[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::account_id"))]
 	pub owner: AccountId,
 	/// Can mint tokens.
+	#[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::account_id"))]
 	pub issuer: AccountId,
 	/// Can thaw tokens, force transfers and burn tokens from any account.
+	#[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::account_id"))]
 	pub admin: AccountId,
 	/// Can freeze tokens.
+	#[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::account_id"))]
 	pub freezer: AccountId,
 	/// The total balance deposited for the all storage associated with this collection.
 	/// Used by `destroy`.
+	#[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::u128_string"))]
 	pub total_deposit: Balance,
 	/// If `true`, then no deposit is needed to hold items of this collection.
 	pub free_holding: bool,
@@ -67,14 +119,18 @@ pub struct CollectionDetails {
 /// Information concerning the ownership of a single unique item.
 #[derive(scale::Decode, scale::Encode, Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ItemDetails {
 	/// The owner of this item.
+	#[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::account_id"))]
 	pub owner: AccountId,
 	/// The approved transferrer of this item, if one is set.
+	#[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::option_account_id"))]
 	pub approved: Option<AccountId>,
 	/// Whether the item can be transferred or not.
 	pub is_frozen: bool,
 	/// The amount held in the pallet's default account for this item. Free-hold items will have
 	/// this as zero.
+	#[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::u128_string"))]
 	pub deposit: Balance,
 }
@@ -8,6 +8,14 @@ import { Region } from 'coretime-utils';
 
 const REGION_COLLECTION_ID = 42;
 
+// The indices of the uniques pallet and its calls in the Astar runtime.
+export const UNIQUES_CALL_INDICES = {
+  pallet: 37,
+  transfer: 5,
+  approveTransfer: 13,
+  cancelApproval: 14,
+};
+
 export async function createRegionCollection(api: ApiPromise, caller: KeyringPair): Promise<void> {
 
   const createCollectionCall = api.tx.uniques.create(REGION_COLLECTION_ID, caller.address);
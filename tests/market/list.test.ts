@@ -18,6 +18,7 @@ import {
   initRegion,
   mintRegion,
   wait,
+  UNIQUES_CALL_INDICES,
 } from '../common';
 
 use(chaiAsPromised);
@@ -44,7 +45,11 @@ describe('Coretime market listing', () => {
     alice = keyring.addFromUri('//Alice');
 
     const xcRegionsFactory = new XcRegions_Factory(api, alice);
-    xcRegions = new XcRegions((await xcRegionsFactory.new()).address, alice, api);
+    xcRegions = new XcRegions(
+      (await xcRegionsFactory.new(REGION_COLLECTION_ID, { uniques: UNIQUES_CALL_INDICES })).address,
+      alice,
+      api,
+    );
 
     const marketFactory = new Market_Factory(api, alice);
     market = new Market(
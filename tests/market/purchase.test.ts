@@ -15,6 +15,7 @@ import {
   expectOnSale,
   initRegion,
   mintRegion,
+  UNIQUES_CALL_INDICES,
 } from '../common';
 import { MarketErrorBuilder } from '../../types/types-returns/coretime_market';
 
@@ -46,7 +47,11 @@ describe('Coretime market purchases', () => {
     charlie = keyring.addFromUri('//Charlie');
 
     const xcRegionsFactory = new XcRegions_Factory(api, alice);
-    xcRegions = new XcRegions((await xcRegionsFactory.new()).address, alice, api);
+    xcRegions = new XcRegions(
+      (await xcRegionsFactory.new(REGION_COLLECTION_ID, { uniques: UNIQUES_CALL_INDICES })).address,
+      alice,
+      api,
+    );
 
     const marketFactory = new Market_Factory(api, alice);
     market = new Market(
@@ -0,0 +1,141 @@
+// This file is part of RegionX.
+//
+// RegionX is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// RegionX is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with RegionX.  If not, see <https://www.gnu.org/licenses/>.
+
+//! A simulated network of a relay chain, the Coretime chain and the chain on which the contracts
+//! are deployed, for testing the XCM built by the contracts end to end.
+//!
+//! Both parachains share the same runtime. The regions are represented by pallet-uniques items
+//! keyed by the location of pallet-broker, just like the `NonFungiblesAdapter` sees them, and
+//! the relay chain token is the native token of every chain.
+
+#![cfg(test)]
+
+mod parachain;
+mod relay_chain;
+mod tests;
+
+use sp_runtime::{AccountId32, BuildStorage};
+use xcm::latest::prelude::*;
+use xcm_executor::traits::ConvertLocation;
+use xcm_simulator::{decl_test_network, decl_test_parachain, decl_test_relay_chain, TestExt};
+
+pub const ALICE: AccountId32 = AccountId32::new([1u8; 32]);
+/// The account of the contract on the contracts chain.
+pub const CONTRACT: AccountId32 = AccountId32::new([2u8; 32]);
+pub const INITIAL_BALANCE: u128 = 1_000_000_000;
+
+/// The parachain id of the chain on which the contracts are deployed.
+pub const CONTRACTS_PARA_ID: u32 = 2000;
+/// The parachain id of the Coretime chain.
+pub const CORETIME_PARA_ID: u32 = 1005;
+/// The index of pallet-broker in the runtime of the Coretime chain.
+pub const BROKER_PALLET_INDEX: u8 = 50;
+
+decl_test_parachain! {
+	pub struct ContractsChain {
+		Runtime = parachain::Runtime,
+		XcmpMessageHandler = parachain::MsgQueue,
+		DmpMessageHandler = parachain::MsgQueue,
+		new_ext = para_ext(CONTRACTS_PARA_ID),
+	}
+}
+
+decl_test_parachain! {
+	pub struct CoretimeChain {
+		Runtime = parachain::Runtime,
+		XcmpMessageHandler = parachain::MsgQueue,
+		DmpMessageHandler = parachain::MsgQueue,
+		new_ext = para_ext(CORETIME_PARA_ID),
+	}
+}
+
+decl_test_relay_chain! {
+	pub struct Relay {
+		Runtime = relay_chain::Runtime,
+		RuntimeCall = relay_chain::RuntimeCall,
+		RuntimeEvent = relay_chain::RuntimeEvent,
+		XcmConfig = relay_chain::XcmConfig,
+		MessageQueue = relay_chain::MessageQueue,
+		System = relay_chain::System,
+		new_ext = relay_ext(),
+	}
+}
+
+decl_test_network! {
+	pub struct MockNet {
+		relay_chain = Relay,
+		parachains = vec![
+			(2000, ContractsChain),
+			(1005, CoretimeChain),
+		],
+	}
+}
+
+/// The sovereign account of the parachain `para_id` on a sibling parachain.
+pub fn sibling_account_id(para_id: u32) -> parachain::AccountId {
+	let location = MultiLocation::new(1, X1(Parachain(para_id)));
+	parachain::LocationToAccountId::convert_location(&location).unwrap()
+}
+
+/// The account a sibling parachain derives from the location of `who` on the parachain
+/// `para_id`.
+pub fn sibling_account_account_id(para_id: u32, who: AccountId32) -> parachain::AccountId {
+	let location = MultiLocation::new(
+		1,
+		X2(Parachain(para_id), Junction::AccountId32 { network: None, id: who.into() }),
+	);
+	parachain::LocationToAccountId::convert_location(&location).unwrap()
+}
+
+pub fn para_ext(para_id: u32) -> sp_io::TestExternalities {
+	use parachain::{MsgQueue, Runtime, System};
+
+	let mut t = frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
+
+	let other_para_id =
+		if para_id == CONTRACTS_PARA_ID { CORETIME_PARA_ID } else { CONTRACTS_PARA_ID };
+	pallet_balances::GenesisConfig::<Runtime> {
+		balances: vec![
+			(ALICE, INITIAL_BALANCE),
+			(CONTRACT, INITIAL_BALANCE),
+			(sibling_account_id(other_para_id), INITIAL_BALANCE),
+		],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		MsgQueue::set_para_id(para_id.into());
+	});
+	ext
+}
+
+pub fn relay_ext() -> sp_io::TestExternalities {
+	use relay_chain::{Runtime, System};
+
+	let mut t = frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
+
+	pallet_balances::GenesisConfig::<Runtime> { balances: vec![(ALICE, INITIAL_BALANCE)] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+pub type ParachainPalletXcm = pallet_xcm::Pallet<parachain::Runtime>;
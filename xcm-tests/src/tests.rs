@@ -0,0 +1,191 @@
+// This file is part of RegionX.
+//
+// RegionX is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// RegionX is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with RegionX.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+	parachain::{self, Balances, ForeignUniques, RuntimeOrigin},
+	sibling_account_account_id, sibling_account_id, ContractsChain, CoretimeChain, MockNet,
+	ParachainPalletXcm, ALICE, BROKER_PALLET_INDEX, CONTRACT, CONTRACTS_PARA_ID, CORETIME_PARA_ID,
+	INITIAL_BALANCE,
+};
+use contracts_xcm::v3::{
+	Junction as ContractsJunction, Junctions, MultiLocation as ContractsLocation,
+};
+use frame_support::{assert_ok, weights::Weight};
+use scale::{Decode, Encode};
+use xcm::{latest::prelude::*, VersionedXcm};
+use xcm_simulator::TestExt;
+
+const REGION: u128 = 42;
+const FEE: u128 = 1_000;
+
+/// The location of the regions collection on the Coretime chain.
+fn local_regions() -> MultiLocation {
+	MultiLocation::new(0, X1(PalletInstance(BROKER_PALLET_INDEX)))
+}
+
+/// The location of the regions collection on the contracts chain.
+fn foreign_regions() -> MultiLocation {
+	MultiLocation::new(1, X2(Parachain(CORETIME_PARA_ID), PalletInstance(BROKER_PALLET_INDEX)))
+}
+
+/// The location of the Coretime chain, as passed to the primitives by the contract.
+fn coretime_reserve() -> ContractsLocation {
+	ContractsLocation::new(1, Junctions::X1(ContractsJunction::Parachain(CORETIME_PARA_ID)))
+}
+
+/// Registers `REGION` on both chains, as it would be after being reserve transferred to the
+/// contract: the sovereign account of the contracts chain holds it on the Coretime chain and the
+/// contract holds its derivative on the contracts chain.
+fn setup_region() {
+	CoretimeChain::execute_with(|| {
+		assert_ok!(ForeignUniques::force_create(
+			RuntimeOrigin::root(),
+			local_regions(),
+			ALICE,
+			true
+		));
+		assert_ok!(ForeignUniques::mint(
+			RuntimeOrigin::signed(ALICE),
+			local_regions(),
+			AssetInstance::Index(REGION),
+			sibling_account_id(CONTRACTS_PARA_ID),
+		));
+	});
+
+	ContractsChain::execute_with(|| {
+		assert_ok!(ForeignUniques::force_create(
+			RuntimeOrigin::root(),
+			foreign_regions(),
+			ALICE,
+			true
+		));
+		assert_ok!(ForeignUniques::mint(
+			RuntimeOrigin::signed(ALICE),
+			foreign_regions(),
+			AssetInstance::Index(REGION),
+			CONTRACT,
+		));
+	});
+}
+
+/// Executes the XCM built by the primitives from the account of the contract, like the contract
+/// does through `XcmCall::Execute`.
+fn execute_as_contract(message: contracts_xcm::v3::Xcm<()>) {
+	// The primitives build the messages with the version of XCM used by the contracts, which is
+	// converted through its encoding.
+	let encoded = contracts_xcm::VersionedXcm::<()>::V3(message).encode();
+	let message = VersionedXcm::<parachain::RuntimeCall>::decode(&mut &encoded[..]).unwrap();
+
+	assert_ok!(ParachainPalletXcm::execute(
+		RuntimeOrigin::signed(CONTRACT),
+		Box::new(message),
+		Weight::from_parts(1_000_000_000, 1_000_000),
+	));
+}
+
+#[test]
+fn reserve_withdraw_region_works() {
+	MockNet::reset();
+	setup_region();
+
+	let message = primitives::pallet_xcm::reserve_withdraw_region(
+		coretime_reserve(),
+		BROKER_PALLET_INDEX,
+		REGION,
+		FEE,
+		&ink::primitives::AccountId::from(<[u8; 32]>::from(ALICE)),
+	)
+	.unwrap();
+
+	ContractsChain::execute_with(|| {
+		execute_as_contract(message);
+
+		// The derivative is burned and the fee is paid by the contract.
+		assert_eq!(ForeignUniques::owner(foreign_regions(), AssetInstance::Index(REGION)), None);
+		assert_eq!(Balances::free_balance(&CONTRACT), INITIAL_BALANCE - FEE);
+	});
+
+	CoretimeChain::execute_with(|| {
+		// The region is released to the beneficiary, along with the fee left after execution.
+		assert_eq!(
+			ForeignUniques::owner(local_regions(), AssetInstance::Index(REGION)),
+			Some(ALICE)
+		);
+		assert_eq!(
+			Balances::free_balance(&sibling_account_id(CONTRACTS_PARA_ID)),
+			INITIAL_BALANCE - FEE
+		);
+		assert!(Balances::free_balance(&ALICE) > INITIAL_BALANCE);
+	});
+}
+
+#[test]
+fn reserve_withdraw_region_to_sibling_account_works() {
+	MockNet::reset();
+	setup_region();
+
+	let contract = ink::primitives::AccountId::from(<[u8; 32]>::from(CONTRACT));
+	let message = primitives::pallet_xcm::reserve_withdraw_region_to(
+		coretime_reserve(),
+		BROKER_PALLET_INDEX,
+		REGION,
+		FEE,
+		primitives::pallet_xcm::sibling_account(CONTRACTS_PARA_ID, &contract),
+	)
+	.unwrap();
+
+	ContractsChain::execute_with(|| {
+		execute_as_contract(message);
+
+		assert_eq!(ForeignUniques::owner(foreign_regions(), AssetInstance::Index(REGION)), None);
+	});
+
+	CoretimeChain::execute_with(|| {
+		// The region is deposited to the account the Coretime chain derives for the contract.
+		assert_eq!(
+			ForeignUniques::owner(local_regions(), AssetInstance::Index(REGION)),
+			Some(sibling_account_account_id(CONTRACTS_PARA_ID, CONTRACT))
+		);
+	});
+}
+
+#[test]
+fn reserve_withdraw_region_without_fee_works() {
+	MockNet::reset();
+	setup_region();
+
+	let message = primitives::pallet_xcm::reserve_withdraw_region(
+		coretime_reserve(),
+		BROKER_PALLET_INDEX,
+		REGION,
+		0,
+		&ink::primitives::AccountId::from(<[u8; 32]>::from(ALICE)),
+	)
+	.unwrap();
+
+	ContractsChain::execute_with(|| {
+		execute_as_contract(message);
+
+		assert_eq!(ForeignUniques::owner(foreign_regions(), AssetInstance::Index(REGION)), None);
+		assert_eq!(Balances::free_balance(&CONTRACT), INITIAL_BALANCE);
+	});
+
+	CoretimeChain::execute_with(|| {
+		assert_eq!(
+			ForeignUniques::owner(local_regions(), AssetInstance::Index(REGION)),
+			Some(ALICE)
+		);
+	});
+}